@@ -0,0 +1,4010 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use log::{debug, info, warn};
+use rand::distributions::{Distribution, Uniform};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use schnorrkel::vrf::{VRFInOut, VRFPreOut, VRFProof};
+use schnorrkel::{signing_context, Keypair};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Persentase stake yang dipotong untuk pelanggaran pertama, kedua, dan ketiga;
+// pelanggaran berikutnya berujung larangan permanen
+pub const ESCALATING_SLASH_PERCENTS: [u64; 3] = [5, 15, 40];
+
+// Batas atas reputasi validator; batas bawahnya adalah 0.0
+pub const MAX_REPUTATION: f64 = 2.0;
+
+// Reward default untuk setiap blok yang berhasil divalidasi
+pub const DEFAULT_BLOCK_REWARD: u64 = 10;
+
+// Jumlah periode default sebelum stake yang di-unbond dapat digunakan kembali
+pub const DEFAULT_UNBONDING_PERIODS: u64 = 7;
+
+// Jumlah periode terlewat berturut-turut sebelum validator dipenjara
+pub const DEFAULT_JAIL_THRESHOLD: u64 = 5;
+
+// Reputasi minimum agar validator dapat keluar dari penjara
+pub const UNJAIL_MIN_REPUTATION: f64 = 0.25;
+
+// Jumlah blok default di bawah tinggi final yang riwayat state validatornya masih disimpan
+pub const DEFAULT_HISTORY_WINDOW: u64 = 100;
+
+// Jumlah transaksi maksimum default yang diambil dari mempool untuk satu blok
+pub const DEFAULT_MAX_TXS_PER_BLOCK: usize = 100;
+
+// Jumlah transaksi maksimum default yang ditampung mempool sebelum transaksi ber-fee terendah dibuang
+pub const DEFAULT_MAX_MEMPOOL_SIZE: usize = 10_000;
+
+// Ukuran payload transaksi maksimum default per blok dalam byte
+pub const DEFAULT_MAX_BLOCK_SIZE: usize = 1024 * 1024;
+
+// Selisih maksimum default (ms) timestamp blok di depan waktu lokal
+pub const DEFAULT_MAX_FUTURE_DRIFT_MS: u128 = 15_000;
+// Faktor peluruhan default untuk reputasi di atas nilai netral 1.0 yang diterapkan setiap rotasi
+// Faktor peluruhan reputasi default menuju nilai netral 1.0 yang diterapkan setiap rotasi
+pub const DEFAULT_REPUTATION_DECAY: f64 = 0.99;
+
+// Kenaikan reputasi default untuk setiap blok yang berhasil divalidasi
+pub const DEFAULT_REPUTATION_GAIN: f64 = 0.1;
+
+// Penurunan reputasi default untuk validator yang tidak aktif dalam satu rotasi
+pub const DEFAULT_REPUTATION_PENALTY: f64 = 0.1;
+
+// Stake default yang dipotong dari validator yang tidak aktif dalam satu rotasi
+pub const DEFAULT_INACTIVITY_STAKE_PENALTY: u64 = 1;
+
+// Pemulihan reputasi default per periode bersih untuk validator yang pernah di-slash,
+// dibagi dengan jumlah pelanggarannya
+pub const DEFAULT_REPUTATION_RECOVERY: f64 = 0.05;
+
+// Jumlah periode bersih berturut-turut sebelum reputasi hasil pemulihan boleh melewati 1.0
+pub const DEFAULT_RECOVERY_PERIODS: u64 = 20;
+
+// Jumlah periode pemberitahuan default sebelum perubahan komisi validator berlaku
+pub const DEFAULT_COMMISSION_NOTICE_PERIODS: u64 = 7;
+
+// Jumlah periode pemanasan default sebelum validator baru dapat dipilih; 0 berarti langsung aktif
+pub const DEFAULT_WARMUP_PERIODS: u64 = 0;
+
+// Stake minimum tertinggi yang diterima konfigurasi; di atas 2^53 bobot pemilihan (f64) kehilangan presisi satuan
+pub const MAX_MIN_STAKE: u64 = 1 << 53;
+
+// Panjang epoch default dalam periode; 1 berarti perubahan set validator berlaku setiap periode
+pub const DEFAULT_EPOCH_LENGTH: u64 = 1;
+
+// Parameter ekonomi reward dan penalti yang dapat diatur per blockchain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChainParams {
+    pub block_reward: u64,
+    pub reputation_gain: f64,
+    pub reputation_penalty: f64,
+    pub inactivity_stake_penalty: u64,
+    pub reputation_decay: f64,
+    pub jail_threshold: u64,
+    pub unbonding_periods: u64,
+    pub reputation_recovery: f64,
+    pub recovery_periods: u64,
+    pub max_weight_fraction: f64,
+    pub commission_notice_periods: u64,
+    pub warmup_periods: u64,
+    pub epoch_length: u64,
+    pub default_commission_rate: f64,
+    pub delegated_stake_multiplier: f64,
+}
+
+impl Default for ChainParams {
+    fn default() -> Self {
+        ChainParams {
+            block_reward: DEFAULT_BLOCK_REWARD,
+            reputation_gain: DEFAULT_REPUTATION_GAIN,
+            reputation_penalty: DEFAULT_REPUTATION_PENALTY,
+            inactivity_stake_penalty: DEFAULT_INACTIVITY_STAKE_PENALTY,
+            reputation_decay: DEFAULT_REPUTATION_DECAY,
+            jail_threshold: DEFAULT_JAIL_THRESHOLD,
+            unbonding_periods: DEFAULT_UNBONDING_PERIODS,
+            reputation_recovery: DEFAULT_REPUTATION_RECOVERY,
+            recovery_periods: DEFAULT_RECOVERY_PERIODS,
+            max_weight_fraction: 1.0,
+            commission_notice_periods: DEFAULT_COMMISSION_NOTICE_PERIODS,
+            warmup_periods: DEFAULT_WARMUP_PERIODS,
+            epoch_length: DEFAULT_EPOCH_LENGTH,
+            default_commission_rate: 0.0,
+            delegated_stake_multiplier: 1.0,
+        }
+    }
+}
+
+// Konfigurasi awal blockchain yang diperiksa batas-batasnya oleh Blockchain::try_new
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainConfig {
+    pub finality_threshold: u64,
+    pub hash_algorithm: HashKind,
+    pub params: ChainParams,
+    pub min_stake: u64,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        ChainConfig {
+            finality_threshold: 1,
+            hash_algorithm: HashKind::Sha256,
+            params: ChainParams::default(),
+            min_stake: 0,
+        }
+    }
+}
+
+// Strategi pemilihan produsen blok
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionStrategy {
+    // Acak berbobot stake dan reputasi
+    #[default]
+    WeightedRandom,
+    // Bergiliran melalui active set yang diurutkan berdasarkan id
+    RoundRobin,
+    // Undian VRF atas hash blok sebelumnya, berbobot stake dan reputasi
+    Vrf,
+    // Pemilih yang dipasang lewat Blockchain::set_proposer_selector
+    Custom,
+}
+
+// Informasi chain yang tersedia bagi ProposerSelector saat memilih produsen blok
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionContext<'a> {
+    // Tinggi chain, yaitu id blok yang akan diproduksi
+    pub height: u64,
+    // Seed yang diturunkan dari state chain; pemilih acak sebaiknya memakainya agar setiap node sepakat
+    pub seed: u64,
+    // Bobot pemilihan (stake efektif x reputasi, sudah dibatasi max_weight_fraction) sejajar dengan daftar validator
+    pub weights: &'a [f64],
+}
+
+// Algoritma pemilihan produsen blok yang dapat diganti tanpa mengubah crate. Validator yang diberikan adalah
+// active set yang diurutkan berdasarkan id; harus Send + Sync agar blockchain dapat dipindahkan antar thread dan
+// pemilihnya dapat dibagi dengan salinan chain simulate_attack
+pub trait ProposerSelector: Send + Sync {
+    fn select<'a>(&self, validators: &'a [&'a Validator], ctx: &SelectionContext) -> Option<&'a Validator>;
+}
+
+// Pemilih bawaan: acak berbobot stake dan reputasi menggunakan RNG yang di-seed dari ctx.seed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WeightedRandomSelector;
+
+impl ProposerSelector for WeightedRandomSelector {
+    fn select<'a>(&self, validators: &'a [&'a Validator], ctx: &SelectionContext) -> Option<&'a Validator> {
+        sample_weighted(ctx.weights, &mut StdRng::seed_from_u64(ctx.seed)).map(|index| validators[index])
+    }
+}
+
+// Pemilih bergiliran berdasarkan tinggi chain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundRobinSelector;
+
+impl ProposerSelector for RoundRobinSelector {
+    fn select<'a>(&self, validators: &'a [&'a Validator], ctx: &SelectionContext) -> Option<&'a Validator> {
+        if validators.is_empty() {
+            return None;
+        }
+        Some(validators[(ctx.height % validators.len() as u64) as usize])
+    }
+}
+
+// Konteks domain untuk VRF pemilihan produsen blok
+pub const VRF_CONTEXT: &[u8] = b"hybrid-stake-proposer";
+
+// Fungsi untuk menyusun input VRF pemilihan produsen: hash blok induk dan slot blok, sehingga periode tanpa
+// validator yang lolos ambang kelayakan tidak mengulang undian yang sama pada periode berikutnya
+pub fn vrf_message(previous_hash: Hash, slot: u64) -> Vec<u8> {
+    let mut message = previous_hash.to_string().into_bytes();
+    message.extend_from_slice(&slot.to_le_bytes());
+    message
+}
+
+// Rata-rata jumlah validator yang lolos ambang kelayakan VRF per blok; peluang tidak ada pemenang e^-3 (sekitar 5%)
+pub const VRF_ELIGIBILITY_RATE: f64 = 3.0;
+
+// Awalan domain untuk hash daun (transaksi) dan node internal pohon Merkle
+const MERKLE_LEAF_PREFIX: u8 = 0x00;
+const MERKLE_NODE_PREFIX: u8 = 0x01;
+
+// Algoritma hash yang digunakan untuk menghitung hash blok
+pub trait HashAlgorithm {
+    fn digest_bytes(&self, input: &[u8]) -> String;
+
+    fn digest(&self, input: &str) -> String {
+        self.digest_bytes(input.as_bytes())
+    }
+}
+
+// MD5 dipertahankan hanya untuk kompatibilitas dengan chain lama
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Md5;
+
+impl HashAlgorithm for Md5 {
+    fn digest_bytes(&self, input: &[u8]) -> String {
+        format!("{:x}", md5::compute(input))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Sha256;
+
+impl HashAlgorithm for Sha256 {
+    fn digest_bytes(&self, input: &[u8]) -> String {
+        format!("{:x}", sha2::Sha256::digest(input))
+    }
+}
+
+// Penanda algoritma yang disimpan di setiap blok
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HashKind {
+    Md5,
+    Sha256,
+}
+
+impl HashAlgorithm for HashKind {
+    fn digest_bytes(&self, input: &[u8]) -> String {
+        match self {
+            HashKind::Md5 => Md5.digest_bytes(input),
+            HashKind::Sha256 => Sha256.digest_bytes(input),
+        }
+    }
+}
+
+impl HashKind {
+    // Fungsi untuk menghitung hash blok dalam bentuk byte tanpa melewati string hex
+    pub fn digest_hash(&self, input: &[u8]) -> Hash {
+        match self {
+            HashKind::Md5 => Hash::from_bytes(&md5::compute(input).0),
+            HashKind::Sha256 => Hash::from_bytes(&sha2::Sha256::digest(input)),
+        }
+        .expect("digests fit in a Hash")
+    }
+}
+
+// Hash blok ringkas: 32 byte untuk SHA-256 atau 16 byte untuk MD5, ditampilkan dan diserialisasi sebagai hex.
+// Hash kosong (panjang 0) dipakai sebagai previous_hash blok pertama
+#[derive(Clone, Copy, Default, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Hash {
+    bytes: [u8; 32],
+    len: u8,
+}
+
+impl Hash {
+    // Fungsi untuk membuat hash dari byte mentah; None jika lebih dari 32 byte
+    pub fn from_bytes(bytes: &[u8]) -> Option<Hash> {
+        if bytes.len() > 32 {
+            return None;
+        }
+        let mut hash = Hash { bytes: [0; 32], len: bytes.len() as u8 };
+        hash.bytes[..bytes.len()].copy_from_slice(bytes);
+        Some(hash)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Fungsi untuk menghitung jumlah karakter hex '0' di awal hash (target proof-of-work)
+    pub fn leading_zero_nibbles(&self) -> usize {
+        let mut zeros = 0;
+        for byte in self.as_bytes() {
+            if *byte == 0 {
+                zeros += 2;
+                continue;
+            }
+            if *byte < 0x10 {
+                zeros += 1;
+            }
+            break;
+        }
+        zeros
+    }
+}
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.as_bytes() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hash({})", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHashError {
+    InvalidLength(usize),
+    InvalidHex,
+}
+
+impl fmt::Display for ParseHashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseHashError::InvalidLength(len) => write!(f, "hash of {} hex characters is not an even length of at most 64", len),
+            ParseHashError::InvalidHex => write!(f, "hash contains a non-hex character"),
+        }
+    }
+}
+
+impl std::error::Error for ParseHashError {}
+
+impl FromStr for Hash {
+    type Err = ParseHashError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        if !hex.len().is_multiple_of(2) || hex.len() > 64 {
+            return Err(ParseHashError::InvalidLength(hex.len()));
+        }
+        let nibble = |c: u8| (c as char).to_digit(16).map(|digit| digit as u8).ok_or(ParseHashError::InvalidHex);
+        let mut hash = Hash { bytes: [0; 32], len: (hex.len() / 2) as u8 };
+        for (byte, pair) in hash.bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            *byte = nibble(pair[0])? << 4 | nibble(pair[1])?;
+        }
+        Ok(hash)
+    }
+}
+
+impl TryFrom<String> for Hash {
+    type Error = ParseHashError;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        hex.parse()
+    }
+}
+
+impl TryFrom<&str> for Hash {
+    type Error = ParseHashError;
+
+    fn try_from(hex: &str) -> Result<Self, Self::Error> {
+        hex.parse()
+    }
+}
+
+impl From<Hash> for String {
+    fn from(hash: Hash) -> Self {
+        hash.to_string()
+    }
+}
+
+// Sumber waktu untuk timestamp blok; dapat diganti agar hash blok dapat direproduksi.
+// Harus Send + Sync agar blockchain dapat dipindahkan antar thread dan jamnya dapat dibagi dengan salinan chain
+pub trait Clock: Send + Sync {
+    fn now_millis(&self) -> u128;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    // Fungsi untuk mengubah waktu sistem ke milidetik sejak epoch; jam yang diatur sebelum epoch
+    // dibulatkan ke 0 alih-alih membuat panic
+    pub fn millis_since_epoch(time: SystemTime) -> u128 {
+        time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_millis())
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u128 {
+        SystemClock::millis_since_epoch(SystemTime::now())
+    }
+}
+
+// Jam tiruan untuk pengujian; salinannya berbagi waktu yang sama sehingga dapat dimajukan dari luar chain
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    pub fn new(now_millis: u64) -> Self {
+        MockClock {
+            now: Arc::new(AtomicU64::new(now_millis)),
+        }
+    }
+
+    pub fn set(&self, now_millis: u64) {
+        self.now.store(now_millis, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, millis: u64) {
+        self.now.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_millis(&self) -> u128 {
+        self.now.load(Ordering::SeqCst) as u128
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Transaction {
+    from: String,
+    to: String,
+    amount: u64,
+    nonce: u64,
+    memo: String,
+    #[serde(default)]
+    fee: u64,
+}
+
+impl Transaction {
+    pub fn new(from: String, to: String, amount: u64, nonce: u64) -> Self {
+        Transaction {
+            from,
+            to,
+            amount,
+            nonce,
+            memo: String::new(),
+            fee: 0,
+        }
+    }
+
+    // Fungsi untuk menetapkan fee transaksi; fee lebih tinggi didahulukan saat menyusun blok
+    pub fn with_fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    // Fungsi untuk membungkus data bebas menjadi transaksi memo tanpa perpindahan nilai
+    pub fn memo(memo: String) -> Self {
+        Transaction {
+            from: String::new(),
+            to: String::new(),
+            amount: 0,
+            nonce: 0,
+            memo,
+            fee: 0,
+        }
+    }
+
+    // Fungsi untuk menyusun encoding kanonis transaksi yang dipakai untuk hash dan ukuran blok: urutan field
+    // tetap (from, to, amount, fee, nonce, memo), angka sebagai u64 little-endian, dan string diawali panjangnya
+    // dalam u64 little-endian sehingga hasilnya tidak bergantung pada serde maupun isi string
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(48 + self.from.len() + self.to.len() + self.memo.len());
+        push_length_prefixed(&mut bytes, self.from.as_bytes());
+        push_length_prefixed(&mut bytes, self.to.as_bytes());
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.fee.to_le_bytes());
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        push_length_prefixed(&mut bytes, self.memo.as_bytes());
+        bytes
+    }
+
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    pub fn memo_data(&self) -> &str {
+        &self.memo
+    }
+}
+
+// Satu langkah bukti Merkle: hash node saudara beserta posisinya (kiri atau kanan) terhadap node yang dibuktikan.
+// Dalam bukti berbentuk string, langkah ditulis sebagai "L:<hex>" atau "R:<hex>"
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum MerkleStep {
+    Left(Hash),
+    Right(Hash),
+}
+
+impl fmt::Display for MerkleStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleStep::Left(sibling) => write!(f, "L:{}", sibling),
+            MerkleStep::Right(sibling) => write!(f, "R:{}", sibling),
+        }
+    }
+}
+
+impl FromStr for MerkleStep {
+    type Err = ParseHashError;
+
+    fn from_str(step: &str) -> Result<Self, Self::Err> {
+        match step.split_once(':') {
+            Some(("L", sibling)) => Ok(MerkleStep::Left(sibling.parse()?)),
+            Some(("R", sibling)) => Ok(MerkleStep::Right(sibling.parse()?)),
+            _ => Err(ParseHashError::InvalidHex),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Block {
+    id: u64,
+    timestamp: u128,
+    transactions: Vec<Transaction>,
+    merkle_root: Hash,
+    validator_id: String,
+    previous_hash: Hash,
+    hash: Hash,
+    hash_algorithm: HashKind,
+    nonce: u64,
+    signature: Vec<u8>,
+    // Periode undian VRF tempat blok diproduksi; ikut di-hash sehingga input VRF dapat diperiksa dari data blok
+    #[serde(default)]
+    slot: u64,
+    #[serde(default)]
+    vrf_output: Vec<u8>,
+    #[serde(default)]
+    vrf_proof: Vec<u8>,
+}
+
+impl Block {
+    pub fn new(id: u64, data: String, validator_id: String, previous_hash: Hash, hash_algorithm: HashKind) -> Self {
+        Block::new_with_txs(id, vec![Transaction::memo(data)], validator_id, previous_hash, hash_algorithm)
+    }
+
+    // Fungsi untuk membuat blok yang berisi daftar transaksi
+    pub fn new_with_txs(id: u64, transactions: Vec<Transaction>, validator_id: String, previous_hash: Hash, hash_algorithm: HashKind) -> Self {
+        Block::with_timestamp(id, transactions, validator_id, previous_hash, SystemClock.now_millis(), hash_algorithm)
+    }
+
+    // Fungsi untuk membuat blok genesis yang deterministik sehingga semua node menyepakati hash-nya
+    pub fn genesis(hash_algorithm: HashKind) -> Self {
+        let transactions = vec![Transaction::memo("Genesis Block".to_string())];
+        Block::with_timestamp(0, transactions, "genesis".to_string(), Hash::default(), 0, hash_algorithm)
+    }
+
+    // Fungsi untuk membuat blok dengan timestamp yang ditentukan, misalnya dari Clock milik blockchain
+    pub fn with_timestamp(
+        id: u64,
+        transactions: Vec<Transaction>,
+        validator_id: String,
+        previous_hash: Hash,
+        timestamp: u128,
+        hash_algorithm: HashKind,
+    ) -> Self {
+        let merkle_root = Block::compute_merkle_root(hash_algorithm, &transactions);
+        let hash = Block::calculate_hash(hash_algorithm, id, 0, merkle_root, &validator_id, timestamp, previous_hash, 0);
+        Block {
+            id,
+            timestamp,
+            transactions,
+            merkle_root,
+            validator_id,
+            previous_hash,
+            hash,
+            hash_algorithm,
+            nonce: 0,
+            signature: Vec::new(),
+            slot: 0,
+            vrf_output: Vec::new(),
+            vrf_proof: Vec::new(),
+        }
+    }
+
+    // Fungsi untuk menghitung hash header blok dari encoding kanonisnya: urutan field tetap (id, slot, merkle_root,
+    // validator_id, timestamp, previous_hash, nonce), angka little-endian, dan string maupun hash diawali
+    // panjangnya sehingga batas antarfield tidak ambigu (misalnya "Validator1" + 21000 vs "Validator12" + 1000)
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_hash(
+        algorithm: HashKind,
+        id: u64,
+        slot: u64,
+        merkle_root: Hash,
+        validator_id: &str,
+        timestamp: u128,
+        previous_hash: Hash,
+        nonce: u64,
+    ) -> Hash {
+        let mut header = Vec::with_capacity(88 + merkle_root.as_bytes().len() + validator_id.len() + previous_hash.as_bytes().len());
+        header.extend_from_slice(&id.to_le_bytes());
+        header.extend_from_slice(&slot.to_le_bytes());
+        push_length_prefixed(&mut header, merkle_root.as_bytes());
+        push_length_prefixed(&mut header, validator_id.as_bytes());
+        header.extend_from_slice(&timestamp.to_le_bytes());
+        push_length_prefixed(&mut header, previous_hash.as_bytes());
+        header.extend_from_slice(&nonce.to_le_bytes());
+        algorithm.digest_hash(&header)
+    }
+
+    fn compute_hash(&self) -> Hash {
+        Block::calculate_hash(
+            self.hash_algorithm,
+            self.id,
+            self.slot,
+            self.merkle_root,
+            &self.validator_id,
+            self.timestamp,
+            self.previous_hash,
+            self.nonce,
+        )
+    }
+
+    // Fungsi untuk menambah nonce sampai hash diawali `difficulty` karakter hex '0' (proof-of-work)
+    pub fn mine(&mut self, difficulty: usize) {
+        while !self.meets_difficulty(difficulty) {
+            self.nonce += 1;
+            self.hash = self.compute_hash();
+        }
+    }
+
+    // Fungsi untuk mengecek apakah hash blok memenuhi target kesulitan
+    pub fn meets_difficulty(&self, difficulty: usize) -> bool {
+        self.hash.leading_zero_nibbles() >= difficulty
+    }
+
+    // Fungsi untuk menghitung Merkle root dari daftar transaksi
+    pub fn compute_merkle_root(algorithm: HashKind, transactions: &[Transaction]) -> Hash {
+        let mut level: Vec<Hash> = transactions.iter().map(|tx| Block::merkle_leaf(algorithm, tx)).collect();
+        if level.is_empty() {
+            return algorithm.digest_hash(&[]);
+        }
+        while level.len() > 1 {
+            level = Block::merkle_level(algorithm, &level);
+        }
+        level.remove(0)
+    }
+
+    // Daun diberi awalan domain yang berbeda dari node internal agar sebuah node tidak dapat diajukan sebagai transaksi
+    fn merkle_leaf(algorithm: HashKind, tx: &Transaction) -> Hash {
+        let mut bytes = vec![MERKLE_LEAF_PREFIX];
+        bytes.extend_from_slice(&tx.canonical_bytes());
+        algorithm.digest_hash(&bytes)
+    }
+
+    // Anak di-hash sesuai posisinya (kiri lalu kanan) sehingga urutan transaksi ikut terikat pada root
+    fn merkle_parent(algorithm: HashKind, left: Hash, right: Hash) -> Hash {
+        let mut bytes = Vec::with_capacity(1 + left.as_bytes().len() + right.as_bytes().len());
+        bytes.push(MERKLE_NODE_PREFIX);
+        bytes.extend_from_slice(left.as_bytes());
+        bytes.extend_from_slice(right.as_bytes());
+        algorithm.digest_hash(&bytes)
+    }
+
+    // Jika jumlah node ganjil, node terakhir naik ke level berikutnya apa adanya tanpa diduplikasi
+    fn merkle_level(algorithm: HashKind, level: &[Hash]) -> Vec<Hash> {
+        level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => Block::merkle_parent(algorithm, *left, *right),
+                _ => pair[0],
+            })
+            .collect()
+    }
+
+    // Fungsi untuk membuat bukti Merkle bagi transaksi pada indeks tertentu, satu string `MerkleStep` per level
+    pub fn merkle_proof(&self, index: usize) -> Option<Vec<String>> {
+        if index >= self.transactions.len() {
+            return None;
+        }
+        let mut level: Vec<Hash> = self.transactions.iter().map(|tx| Block::merkle_leaf(self.hash_algorithm, tx)).collect();
+        let mut index = index;
+        let mut proof = Vec::new();
+        while level.len() > 1 {
+            // index ^ 1 adalah pasangan node; node terakhir tanpa pasangan naik tanpa langkah bukti
+            if let Some(&sibling) = level.get(index ^ 1) {
+                // Node pada indeks genap adalah anak kiri sehingga saudaranya berada di kanan
+                let step = if index.is_multiple_of(2) { MerkleStep::Right(sibling) } else { MerkleStep::Left(sibling) };
+                proof.push(step.to_string());
+            }
+            level = Block::merkle_level(self.hash_algorithm, &level);
+            index /= 2;
+        }
+        Some(proof)
+    }
+
+    // Fungsi untuk memverifikasi bahwa transaksi termasuk dalam blok menggunakan bukti Merkle; langkah yang tidak
+    // dapat di-parse membuat bukti ditolak
+    pub fn verify_transaction_inclusion(&self, tx: &Transaction, proof: &[String]) -> bool {
+        let leaf = Block::merkle_leaf(self.hash_algorithm, tx);
+        let root = proof.iter().try_fold(leaf, |node, step| match step.parse::<MerkleStep>().ok()? {
+            MerkleStep::Left(sibling) => Some(Block::merkle_parent(self.hash_algorithm, sibling, node)),
+            MerkleStep::Right(sibling) => Some(Block::merkle_parent(self.hash_algorithm, node, sibling)),
+        });
+        root == Some(self.merkle_root)
+    }
+
+    // Fungsi untuk menandatangani hash blok dengan kunci privat validator
+    pub fn sign(&mut self, signing_key: &SigningKey) {
+        self.signature = signing_key.sign(self.hash.to_string().as_bytes()).to_bytes().to_vec();
+    }
+
+    // Fungsi untuk melampirkan slot beserta output dan bukti VRF yang memenangkan undian produsen blok; hash dihitung
+    // ulang karena slot termasuk header
+    pub fn attach_vrf_proof(&mut self, slot: u64, output: Vec<u8>, proof: Vec<u8>) {
+        self.slot = slot;
+        self.vrf_output = output;
+        self.vrf_proof = proof;
+        self.hash = self.compute_hash();
+    }
+
+    // Fungsi untuk memverifikasi tanda tangan blok terhadap kunci publik validator
+    pub fn verify_signature(&self, public_key: &VerifyingKey) -> bool {
+        Signature::from_slice(&self.signature)
+            .map(|signature| public_key.verify(self.hash.to_string().as_bytes(), &signature).is_ok())
+            .unwrap_or(false)
+    }
+
+    // Fungsi untuk menghitung ulang hash dan membandingkannya dengan hash yang tersimpan
+    pub fn verify_hash(&self) -> bool {
+        if Block::compute_merkle_root(self.hash_algorithm, &self.transactions) != self.merkle_root {
+            return false;
+        }
+        self.compute_hash() == self.hash
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn timestamp(&self) -> u128 {
+        self.timestamp
+    }
+
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    pub fn merkle_root(&self) -> Hash {
+        self.merkle_root
+    }
+
+    // Fungsi untuk menghitung ukuran payload transaksi blok dalam byte
+    pub fn payload_size(&self) -> usize {
+        self.transactions.iter().map(|tx| tx.canonical_bytes().len()).sum()
+    }
+
+    pub fn validator_id(&self) -> &str {
+        &self.validator_id
+    }
+
+    pub fn previous_hash(&self) -> Hash {
+        self.previous_hash
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn hash_algorithm(&self) -> HashKind {
+        self.hash_algorithm
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    pub fn vrf_output(&self) -> &[u8] {
+        &self.vrf_output
+    }
+
+    pub fn vrf_proof(&self) -> &[u8] {
+        &self.vrf_proof
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
+
+// Tampilan ringkas blok untuk dicetak; hash dipotong 8 karakter pertama agar mudah dibaca
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hash = self.hash.to_string();
+        let short_hash = hash.get(..8).unwrap_or(&hash);
+        write!(
+            f,
+            "Block #{} [{}] by {} at {}",
+            self.id, short_hash, self.validator_id, self.timestamp
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Validator {
+    id: String,
+    stake: u64,
+    delegated_stake: u64,
+    rotation_period: u64,
+    last_block_validated: u64,
+    reputation: f64,
+    commission_rate: f64,
+    #[serde(default)]
+    pending_commission: Option<(f64, u64)>,
+    pending_unbond: Vec<(u64, u64)>,
+    unbonded_balance: u64,
+    jailed: bool,
+    missed_periods: u64,
+    #[serde(default)]
+    activation_period: u64,
+    #[serde(default)]
+    clean_periods: u64,
+    #[serde(default)]
+    blocks_produced: u64,
+    #[serde(default)]
+    blocks_missed: u64,
+    #[serde(default)]
+    pending_unjail: bool,
+    public_key: Option<[u8; 32]>,
+    #[serde(default)]
+    vrf_public_key: Option<[u8; 32]>,
+}
+
+impl Validator {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn stake(&self) -> u64 {
+        self.stake
+    }
+
+    pub fn delegated_stake(&self) -> u64 {
+        self.delegated_stake
+    }
+
+    pub fn reputation(&self) -> f64 {
+        self.reputation
+    }
+
+    pub fn commission_rate(&self) -> f64 {
+        self.commission_rate
+    }
+
+    // Perubahan komisi yang sudah diumumkan sebagai (tarif baru, periode berlaku)
+    pub fn pending_commission(&self) -> Option<(f64, u64)> {
+        self.pending_commission
+    }
+
+    // Apakah validator sudah meminta keluar dari penjara dan menunggu batas epoch berikutnya
+    pub fn pending_unjail(&self) -> bool {
+        self.pending_unjail
+    }
+
+    pub fn rotation_period(&self) -> u64 {
+        self.rotation_period
+    }
+
+    pub fn last_block_validated(&self) -> u64 {
+        self.last_block_validated
+    }
+
+    // Daftar (jumlah, periode terbuka) stake yang sedang dalam masa unbonding
+    pub fn pending_unbond(&self) -> &[(u64, u64)] {
+        &self.pending_unbond
+    }
+
+    // Stake yang sudah selesai unbonding dan dapat digunakan kembali
+    pub fn unbonded_balance(&self) -> u64 {
+        self.unbonded_balance
+    }
+
+    pub fn jailed(&self) -> bool {
+        self.jailed
+    }
+
+    // Periode pertama validator boleh dipilih sebagai produsen blok
+    pub fn activation_period(&self) -> u64 {
+        self.activation_period
+    }
+
+    // Jumlah periode berturut-turut validator tidak menghasilkan blok
+    pub fn missed_periods(&self) -> u64 {
+        self.missed_periods
+    }
+
+    pub fn blocks_produced(&self) -> u64 {
+        self.blocks_produced
+    }
+
+    pub fn blocks_missed(&self) -> u64 {
+        self.blocks_missed
+    }
+
+    // Kunci publik untuk memverifikasi blok; blok dari validator tanpa kunci selalu ditolak
+    pub fn public_key(&self) -> Option<VerifyingKey> {
+        self.public_key.and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+    }
+
+    // Fungsi untuk mendapatkan jumlah periode berturut-turut tanpa penalti, yang menentukan kapan reputasi
+    // validator yang pernah di-slash boleh pulih melewati 1.0
+    pub fn clean_periods(&self) -> u64 {
+        self.clean_periods
+    }
+
+    pub fn vrf_public_key(&self) -> Option<schnorrkel::PublicKey> {
+        self.vrf_public_key.and_then(|bytes| schnorrkel::PublicKey::from_bytes(&bytes).ok())
+    }
+}
+
+// Tampilan ringkas validator untuk dicetak
+impl fmt::Display for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Validator {} (stake: {}, delegated: {}, reputation: {:.2}, jailed: {})",
+            self.id, self.stake, self.delegated_stake, self.reputation, self.jailed
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TokenHolder {
+    id: String,
+    stake: u64,
+    delegated_to: Vec<(String, u64)>,
+}
+
+impl TokenHolder {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn stake(&self) -> u64 {
+        self.stake
+    }
+
+    pub fn delegated_to(&self) -> &[(String, u64)] {
+        &self.delegated_to
+    }
+}
+
+// Penghitung suara finalitas sebuah blok: validator berbeda yang mendukungnya (produsen dalam jendela
+// finality_threshold blok penerus dan pemberi atestasi) beserta jumlah stake mereka menurut StakeBasis blok itu
+#[derive(Default)]
+struct VoteTally {
+    voters: HashSet<String>,
+    stake: u64,
+    justified: bool,
+}
+
+// Stake suara finalitas yang dibekukan saat sebuah blok diterapkan: stake efektif setiap validator yang boleh
+// memberi suara dan total stake aktif sebagai pembanding. Dukungan untuk blok itu selalu diukur dengan basis ini,
+// baik saat penghitung diperbarui sedikit demi sedikit maupun saat disusun ulang
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct StakeBasis {
+    stakes: HashMap<String, u64>,
+    total: u64,
+}
+
+pub struct Blockchain {
+    blocks: Vec<Block>,
+    hash_index: HashMap<Hash, usize>,
+    side_blocks: HashMap<Hash, Block>,
+    reward_records: HashMap<Hash, RewardRecord>,
+    // Bobot undian VRF active set saat sebuah blok diterapkan, per hash blok; dipakai untuk undian dan verifikasi anaknya
+    vrf_weights: HashMap<Hash, Vec<(String, f64)>>,
+    attestations: HashMap<Hash, HashSet<String>>,
+    pending_blocks: HashSet<Block>,
+    mempool: Vec<Transaction>,
+    max_txs_per_block: usize,
+    max_mempool_size: usize,
+    max_block_size: usize,
+    difficulty: usize,
+    max_future_drift_ms: u128,
+    validators: HashMap<String, Validator>,
+    token_holders: HashMap<String, TokenHolder>,
+    accounts: HashMap<String, u64>,
+    account_nonces: HashMap<String, u64>,
+    current_period: u64,
+    periods_since_last_block: u64,
+    stall_threshold: u64,
+    // Riwayat state validator per tinggi blok sebagai (id blok ujung, snapshot semua validator)
+    validator_history: Vec<(u64, Vec<ValidatorSnapshot>)>,
+    history_window: u64,
+    finality_threshold: u64,
+    justified_height: Option<u64>,
+    finalized_height: Option<u64>,
+    // Penghitung suara finalitas per id blok, hanya untuk blok di atas blok final
+    vote_tallies: BTreeMap<u64, VoteTally>,
+    // Basis stake suara per id blok, hanya untuk blok di atas blok final
+    stake_bases: BTreeMap<u64, StakeBasis>,
+    min_stake: u64,
+    max_active_validators: usize,
+    // Id anggota active set yang dibekukan saat blok pertama suatu epoch diterapkan, sebagai (epoch, id)
+    epoch_active_set: Option<(u64, Vec<String>)>,
+    selection_strategy: SelectionStrategy,
+    params: ChainParams,
+    halving_interval: u64,
+    total_supply: u64,
+    supply_baseline: u64,
+    burn_penalties: bool,
+    treasury: u64,
+    // Stake validator yang sudah keluar namun masih unbonding, sebagai (akun payout, jumlah, periode lepas)
+    pending_exits: Vec<(String, u64, u64)>,
+    seed: Option<u64>,
+    thread_rng_selection: bool,
+    hash_algorithm: HashKind,
+    security_measures: SecurityMeasures,
+    signing_keys: HashMap<String, SigningKey>,
+    vrf_keys: HashMap<String, Keypair>,
+    clock: Arc<dyn Clock>,
+    listeners: Vec<EventListener>,
+    proposer_selector: Arc<dyn ProposerSelector>,
+}
+
+// Fungsi untuk menurunkan bilangan 64-bit deterministik dari hash blok (dalam bentuk hex)
+fn hash_to_u64(hash: Hash) -> u64 {
+    let digest = sha2::Sha256::digest(hash.to_string().as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+// Fungsi untuk menambahkan data ke encoding kanonis dengan awalan panjang u64 little-endian
+fn push_length_prefixed(bytes: &mut Vec<u8>, value: &[u8]) {
+    bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(value);
+}
+
+// Fungsi untuk memeriksa faktor peluruhan reputasi berada di rentang (0, 1]; NaN ikut ditolak
+fn check_reputation_decay(decay: f64) -> Result<(), ConfigError> {
+    if decay > 0.0 && decay <= 1.0 {
+        Ok(())
+    } else {
+        Err(ConfigError::DecayFactorOutOfRange(decay))
+    }
+}
+
+// Fungsi untuk mengubah output VRF menjadi skor undian -ln(u)/bobot; None jika -ln(u) melewati ambang
+// VRF_ELIGIBILITY_RATE * porsi bobot, sehingga peluang lolos validator sebanding dengan porsi bobotnya
+fn vrf_score(inout: &VRFInOut, weight: f64, total_weight: f64) -> Option<f64> {
+    if weight <= 0.0 || total_weight <= 0.0 {
+        return None;
+    }
+    let draw = u64::from_le_bytes(inout.make_bytes::<[u8; 8]>(VRF_CONTEXT));
+    let uniform = (draw as f64 + 1.0) / (u64::MAX as f64 + 2.0);
+    let exponential = -uniform.ln();
+    (exponential <= VRF_ELIGIBILITY_RATE * weight / total_weight).then_some(exponential / weight)
+}
+
+// Fungsi untuk memilih indeks secara acak berbobot tanpa alokasi. Hasil dan pemakaian RNG-nya identik dengan
+// rand::distributions::WeightedIndex (satu sampel Uniform[0, total), lalu indeks pertama yang bobot kumulatifnya
+// melebihi sampel), sehingga pemilihan ber-seed tetap sama. None jika kosong, ada bobot negatif/NaN, atau semuanya 0
+fn sample_weighted<R: Rng>(weights: &[f64], rng: &mut R) -> Option<usize> {
+    if weights.iter().any(|weight| weight.is_nan() || *weight < 0.0) {
+        return None;
+    }
+    let total: f64 = weights.iter().sum();
+    if weights.is_empty() || total == 0.0 {
+        return None;
+    }
+    let chosen = Uniform::new(0.0, total).sample(rng);
+    let mut cumulative = 0.0;
+    for (index, weight) in weights[..weights.len() - 1].iter().enumerate() {
+        cumulative += weight;
+        if cumulative > chosen {
+            return Some(index);
+        }
+    }
+    Some(weights.len() - 1)
+}
+
+// Representasi blockchain yang dapat diserialisasi untuk disimpan ke disk
+#[derive(Serialize, Deserialize)]
+struct ChainSnapshot {
+    blocks: Vec<Block>,
+    #[serde(default)]
+    reward_records: HashMap<Hash, RewardRecord>,
+    #[serde(default)]
+    vrf_weights: HashMap<Hash, Vec<(String, f64)>>,
+    #[serde(default)]
+    attestations: HashMap<Hash, HashSet<String>>,
+    max_txs_per_block: usize,
+    #[serde(default = "default_max_mempool_size")]
+    max_mempool_size: usize,
+    max_block_size: usize,
+    difficulty: usize,
+    max_future_drift_ms: u128,
+    validators: Vec<Validator>,
+    token_holders: Vec<TokenHolder>,
+    #[serde(default)]
+    accounts: HashMap<String, u64>,
+    #[serde(default)]
+    account_nonces: HashMap<String, u64>,
+    current_period: u64,
+    #[serde(default)]
+    periods_since_last_block: u64,
+    #[serde(default)]
+    stall_threshold: u64,
+    #[serde(default)]
+    validator_history: Vec<(u64, Vec<ValidatorSnapshot>)>,
+    #[serde(default = "default_history_window")]
+    history_window: u64,
+    finality_threshold: u64,
+    justified_height: Option<u64>,
+    finalized_height: Option<u64>,
+    #[serde(default)]
+    stake_bases: BTreeMap<u64, StakeBasis>,
+    min_stake: u64,
+    #[serde(default)]
+    max_active_validators: usize,
+    #[serde(default)]
+    epoch_active_set: Option<(u64, Vec<String>)>,
+    #[serde(default)]
+    selection_strategy: SelectionStrategy,
+    params: ChainParams,
+    halving_interval: u64,
+    total_supply: u64,
+    supply_baseline: u64,
+    burn_penalties: bool,
+    treasury: u64,
+    #[serde(default)]
+    pending_exits: Vec<(String, u64, u64)>,
+    seed: Option<u64>,
+    #[serde(default)]
+    thread_rng_selection: bool,
+    hash_algorithm: HashKind,
+    security_measures: SecurityMeasures,
+}
+
+// Checkpoint tepercaya untuk sinkronisasi cepat: blok sampai tinggi final beserta state pada tinggi tersebut
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    height: u64,
+    snapshot: ChainSnapshot,
+}
+
+impl Checkpoint {
+    // Fungsi untuk mendapatkan id blok terakhir yang tercakup checkpoint
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+}
+
+fn default_max_mempool_size() -> usize {
+    DEFAULT_MAX_MEMPOOL_SIZE
+}
+
+fn default_history_window() -> u64 {
+    DEFAULT_HISTORY_WINDOW
+}
+
+// Catatan efek reward sebuah blok agar dapat dibatalkan saat reorg
+#[derive(Clone, Serialize, Deserialize)]
+struct RewardRecord {
+    validator_id: String,
+    validator_reward: u64,
+    delegator_rewards: Vec<(String, u64)>,
+    previous_reputation: f64,
+    previous_last_block_validated: u64,
+    #[serde(default)]
+    fees: u64,
+}
+
+// Peristiwa yang dipancarkan blockchain kepada listener yang terdaftar
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainEvent {
+    BlockValidated { block_id: u64, validator_id: String, reward: u64 },
+    ValidatorRotated { period: u64, penalized: Vec<String> },
+    Slashed { validator_id: String, amount: u64 },
+    FinalityReached { finalized_height: u64, block_ids: Vec<u64> },
+    Stalled { periods_since_last_block: u64 },
+}
+
+// Ringkasan kesehatan chain untuk keperluan monitoring
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainStats {
+    pub total_blocks: u64,
+    pub active_validators: usize,
+    pub jailed_validators: usize,
+    pub total_stake: u64,
+    pub total_delegated_stake: u64,
+    pub average_reputation: f64,
+    pub justified_height: Option<u64>,
+    pub finalized_height: Option<u64>,
+}
+
+// State validator yang tercatat pada akhir sebuah periode, untuk audit historis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorSnapshot {
+    pub id: String,
+    pub period: u64,
+    pub stake: u64,
+    pub delegated_stake: u64,
+    pub reputation: f64,
+    pub jailed: bool,
+}
+
+// Ringkasan ujung chain dan finalitasnya untuk endpoint status node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeadInfo {
+    pub head_id: Option<u64>,
+    pub head_hash: Option<Hash>,
+    pub justified_height: Option<u64>,
+    pub finalized_height: Option<u64>,
+    pub total_blocks: u64,
+    pub stalled: bool,
+}
+
+// Permintaan baca-saja terhadap chain, cikal bakal lapisan RPC tanpa terikat transport tertentu
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum Query {
+    GetBlock { id: u64 },
+    GetValidator { id: String },
+    GetHead,
+    GetStats,
+    ListValidators,
+}
+
+// Jawaban atas Query; blok atau validator yang tidak ditemukan dikembalikan sebagai null
+#[derive(Clone, Serialize)]
+#[serde(tag = "result", content = "data")]
+pub enum QueryResponse {
+    Block(Option<Block>),
+    Validator(Option<Validator>),
+    Head(HeadInfo),
+    Stats(ChainStats),
+    Validators(Vec<Validator>),
+}
+
+impl QueryResponse {
+    // Fungsi untuk menserialisasi jawaban ke JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("query responses always serialize to JSON")
+    }
+}
+
+// Ringkasan hasil simulate setelah menjalankan sejumlah periode
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub periods: u64,
+    pub blocks_produced: u64,
+    pub failed_periods: u64,
+    pub blocks_by_validator: HashMap<String, u64>,
+    pub slashes: Vec<(String, u64)>,
+    pub finalized_height: Option<u64>,
+}
+
+// Ringkasan hasil simulate_attack: blok yang dikuasai penyerang dan respons mekanisme keamanan
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackReport {
+    pub periods: u64,
+    pub blocks_produced: u64,
+    pub attacker_blocks: u64,
+    pub double_signs_detected: u64,
+    pub slashes: Vec<(String, u64)>,
+    pub banned: Vec<String>,
+}
+
+pub type EventListener = Box<dyn FnMut(&ChainEvent) + Send>;
+
+// Hasil penerimaan blok dari jaringan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiveOutcome {
+    Extended,
+    Forked,
+    Reorged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    UnknownValidator(String),
+    BadParentHash,
+    BadHash,
+    DuplicateBlockId(u64),
+    UnexpectedBlockId { expected: u64, found: u64 },
+    BlockTooLarge { size: usize, max: usize },
+    BadSignature,
+    BadVrfProof,
+    InsufficientWork,
+    BadTimestamp,
+    InvalidTransaction(TransactionError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    InsufficientBalance { account: String, balance: u64, required: u64 },
+    BadNonce { account: String, expected: u64, found: u64 },
+    FeeOverflow,
+}
+
+// Perubahan saldo dan nonce akun yang belum diterapkan ke blockchain, beserta fee yang sudah didebit
+#[derive(Default)]
+struct AccountChanges {
+    balances: HashMap<String, u64>,
+    nonces: HashMap<String, u64>,
+    fees: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainError {
+    UnknownValidator(String),
+    ReputationTooLow { reputation: f64, required: f64 },
+    NoEligibleValidator,
+    InvalidBlock(ValidationError),
+    ValidatorExists(String),
+    ValidatorJailed(String),
+    UnknownBlock(Hash),
+    RollbackTooDeep { requested: usize, available: usize },
+    WouldRevertFinalized(u64),
+    PruneBeyondFinalized { keep_after: u64, finalized_height: Option<u64> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    ZeroFinalityThreshold,
+    ZeroEpochLength,
+    CommissionRateOutOfRange(f64),
+    DecayFactorOutOfRange(f64),
+    DelegatedStakeMultiplierOutOfRange(f64),
+    MinStakeTooHigh { min_stake: u64, max: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeError {
+    UnknownValidator(String),
+    InsufficientStake { available: u64, requested: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DelegationError {
+    ExceedsStake { stake: u64, delegated: u64 },
+    HolderExists(String),
+    UnknownHolder(String),
+    UnknownValidator(String),
+}
+
+// Error gabungan untuk seluruh crate sehingga pemakai dapat memakai `?` pada berbagai jenis error sekaligus
+#[derive(Debug, Clone, PartialEq)]
+pub enum HybridStakeError {
+    Validation(ValidationError),
+    Transaction(TransactionError),
+    Consensus(ChainError),
+    Config(ConfigError),
+    Stake(StakeError),
+    Delegation(DelegationError),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::UnknownValidator(id) => write!(f, "unknown validator {}", id),
+            ValidationError::BadParentHash => write!(f, "previous hash does not match the parent block"),
+            ValidationError::BadHash => write!(f, "block hash does not match its contents"),
+            ValidationError::DuplicateBlockId(id) => write!(f, "block #{} already exists", id),
+            ValidationError::UnexpectedBlockId { expected, found } => write!(f, "expected block #{}, found #{}", expected, found),
+            ValidationError::BlockTooLarge { size, max } => write!(f, "block payload of {} bytes exceeds the limit of {}", size, max),
+            ValidationError::BadSignature => write!(f, "block signature is missing or invalid"),
+            ValidationError::BadVrfProof => write!(f, "VRF proof is missing or invalid"),
+            ValidationError::InsufficientWork => write!(f, "block hash does not meet the difficulty"),
+            ValidationError::BadTimestamp => write!(f, "block timestamp is before its parent or too far in the future"),
+            ValidationError::InvalidTransaction(err) => write!(f, "invalid transaction: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ValidationError::InvalidTransaction(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionError::InsufficientBalance { account, balance, required } => {
+                write!(f, "account {} has balance {} but needs {}", account, balance, required)
+            }
+            TransactionError::BadNonce { account, expected, found } => {
+                write!(f, "account {} expected nonce {}, found {}", account, expected, found)
+            }
+            TransactionError::FeeOverflow => write!(f, "total block fees overflow"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainError::UnknownValidator(id) => write!(f, "unknown validator {}", id),
+            ChainError::ReputationTooLow { reputation, required } => {
+                write!(f, "reputation {} is below the required {}", reputation, required)
+            }
+            ChainError::NoEligibleValidator => write!(f, "no eligible validator"),
+            ChainError::InvalidBlock(err) => write!(f, "invalid block: {}", err),
+            ChainError::ValidatorExists(id) => write!(f, "validator {} already exists", id),
+            ChainError::ValidatorJailed(id) => write!(f, "validator {} is jailed", id),
+            ChainError::UnknownBlock(hash) => write!(f, "unknown block {}", hash),
+            ChainError::RollbackTooDeep { requested, available } => {
+                write!(f, "cannot roll back {} blocks, only {} available", requested, available)
+            }
+            ChainError::WouldRevertFinalized(height) => write!(f, "would revert blocks finalized up to #{}", height),
+            ChainError::PruneBeyondFinalized { keep_after, finalized_height } => match finalized_height {
+                Some(height) => write!(f, "cannot prune below #{} beyond the finalized block #{}", keep_after, height),
+                None => write!(f, "cannot prune below #{} before any block is finalized", keep_after),
+            },
+        }
+    }
+}
+
+impl std::error::Error for ChainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChainError::InvalidBlock(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::ZeroFinalityThreshold => write!(f, "finality threshold must be at least 1"),
+            ConfigError::ZeroEpochLength => write!(f, "epoch length must be at least 1"),
+            ConfigError::CommissionRateOutOfRange(rate) => write!(f, "commission rate {} is outside [0, 1]", rate),
+            ConfigError::DecayFactorOutOfRange(decay) => write!(f, "reputation decay factor {} is outside (0, 1]", decay),
+            ConfigError::DelegatedStakeMultiplierOutOfRange(multiplier) => {
+                write!(f, "delegated stake multiplier {} must be finite and non-negative", multiplier)
+            }
+            ConfigError::MinStakeTooHigh { min_stake, max } => write!(f, "minimum stake {} exceeds the maximum of {}", min_stake, max),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl fmt::Display for StakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StakeError::UnknownValidator(id) => write!(f, "unknown validator {}", id),
+            StakeError::InsufficientStake { available, requested } => {
+                write!(f, "requested {} but only {} stake is available", requested, available)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StakeError {}
+
+impl fmt::Display for DelegationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DelegationError::ExceedsStake { stake, delegated } => write!(f, "delegating {} exceeds the holder's stake of {}", delegated, stake),
+            DelegationError::HolderExists(id) => write!(f, "token holder {} already exists", id),
+            DelegationError::UnknownHolder(id) => write!(f, "unknown token holder {}", id),
+            DelegationError::UnknownValidator(id) => write!(f, "unknown validator {}", id),
+        }
+    }
+}
+
+impl std::error::Error for DelegationError {}
+
+impl fmt::Display for HybridStakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HybridStakeError::Validation(err) => write!(f, "validation error: {}", err),
+            HybridStakeError::Transaction(err) => write!(f, "transaction error: {}", err),
+            HybridStakeError::Consensus(err) => write!(f, "consensus error: {}", err),
+            HybridStakeError::Config(err) => write!(f, "configuration error: {}", err),
+            HybridStakeError::Stake(err) => write!(f, "stake error: {}", err),
+            HybridStakeError::Delegation(err) => write!(f, "delegation error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HybridStakeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HybridStakeError::Validation(err) => Some(err),
+            HybridStakeError::Transaction(err) => Some(err),
+            HybridStakeError::Consensus(err) => Some(err),
+            HybridStakeError::Config(err) => Some(err),
+            HybridStakeError::Stake(err) => Some(err),
+            HybridStakeError::Delegation(err) => Some(err),
+        }
+    }
+}
+
+impl From<ValidationError> for HybridStakeError {
+    fn from(err: ValidationError) -> Self {
+        HybridStakeError::Validation(err)
+    }
+}
+
+impl From<TransactionError> for HybridStakeError {
+    fn from(err: TransactionError) -> Self {
+        HybridStakeError::Transaction(err)
+    }
+}
+
+impl From<ChainError> for HybridStakeError {
+    fn from(err: ChainError) -> Self {
+        HybridStakeError::Consensus(err)
+    }
+}
+
+impl From<ConfigError> for HybridStakeError {
+    fn from(err: ConfigError) -> Self {
+        HybridStakeError::Config(err)
+    }
+}
+
+impl From<StakeError> for HybridStakeError {
+    fn from(err: StakeError) -> Self {
+        HybridStakeError::Stake(err)
+    }
+}
+
+impl From<DelegationError> for HybridStakeError {
+    fn from(err: DelegationError) -> Self {
+        HybridStakeError::Delegation(err)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SecurityMeasures {
+    malicious_activity_log: HashSet<String>,
+    validator_penalties: HashMap<String, u64>,
+    #[serde(default)]
+    reported_double_signs: HashSet<(String, u64)>,
+}
+
+impl Blockchain {
+    // Fungsi untuk membuat blockchain; panik jika finality_threshold 0, gunakan try_new untuk menangani error
+    pub fn new(finality_threshold: u64) -> Self {
+        Blockchain::try_new(ChainConfig { finality_threshold, ..ChainConfig::default() }).expect("invalid chain configuration")
+    }
+
+    // Fungsi untuk membuat blockchain dari konfigurasi yang diperiksa terlebih dahulu
+    pub fn try_new(config: ChainConfig) -> Result<Self, HybridStakeError> {
+        if config.finality_threshold == 0 {
+            return Err(ConfigError::ZeroFinalityThreshold.into());
+        }
+        if config.params.epoch_length == 0 {
+            return Err(ConfigError::ZeroEpochLength.into());
+        }
+        let commission_rate = config.params.default_commission_rate;
+        if !(0.0..=1.0).contains(&commission_rate) {
+            return Err(ConfigError::CommissionRateOutOfRange(commission_rate).into());
+        }
+        check_reputation_decay(config.params.reputation_decay)?;
+        let multiplier = config.params.delegated_stake_multiplier;
+        if !(multiplier.is_finite() && multiplier >= 0.0) {
+            return Err(ConfigError::DelegatedStakeMultiplierOutOfRange(multiplier).into());
+        }
+        if config.min_stake > MAX_MIN_STAKE {
+            return Err(ConfigError::MinStakeTooHigh { min_stake: config.min_stake, max: MAX_MIN_STAKE }.into());
+        }
+        let mut blockchain = Blockchain::with_hash_algorithm(config.finality_threshold, config.hash_algorithm);
+        blockchain.params = config.params;
+        blockchain.min_stake = config.min_stake;
+        Ok(blockchain)
+    }
+
+    // Fungsi untuk membuat blockchain yang diawali dengan blok genesis
+    pub fn new_with_genesis(finality_threshold: u64) -> Self {
+        let mut blockchain = Blockchain::new(finality_threshold);
+        let genesis = Block::genesis(blockchain.hash_algorithm);
+        blockchain.hash_index.insert(genesis.hash, 0);
+        blockchain.blocks.push(genesis);
+        blockchain
+    }
+
+    // Fungsi untuk membuat blockchain dengan reward blok tertentu
+    pub fn with_block_reward(finality_threshold: u64, block_reward: u64) -> Self {
+        let mut blockchain = Blockchain::new(finality_threshold);
+        blockchain.params.block_reward = block_reward;
+        blockchain
+    }
+
+    // Fungsi untuk membuat blockchain dengan parameter ekonomi tertentu; panik jika parameternya tidak valid,
+    // gunakan try_new untuk menangani error
+    pub fn with_params(finality_threshold: u64, params: ChainParams) -> Self {
+        Blockchain::try_new(ChainConfig { finality_threshold, params, ..ChainConfig::default() }).expect("invalid chain configuration")
+    }
+
+    pub fn params(&self) -> &ChainParams {
+        &self.params
+    }
+
+    pub fn finality_threshold(&self) -> u64 {
+        self.finality_threshold
+    }
+
+    // Fungsi untuk mengubah jumlah blok penerus yang dihitung sebagai dukungan finalitas, lalu langsung
+    // mengecek ulang finalitas; 0 dinaikkan menjadi 1 agar blok penerus tetap dihitung. Finalitas yang sudah
+    // tercapai tidak pernah dibatalkan, walaupun ambang diturunkan
+    pub fn set_finality_threshold(&mut self, finality_threshold: u64) -> Vec<u64> {
+        self.finality_threshold = finality_threshold.max(1);
+        self.check_finality()
+    }
+
+    pub fn min_stake(&self) -> u64 {
+        self.min_stake
+    }
+
+    pub fn difficulty(&self) -> usize {
+        self.difficulty
+    }
+
+    pub fn selection_strategy(&self) -> SelectionStrategy {
+        self.selection_strategy
+    }
+
+    // Fungsi untuk memulai BlockchainBuilder
+    pub fn builder() -> BlockchainBuilder {
+        BlockchainBuilder::new()
+    }
+
+    // Fungsi untuk membuat blockchain dengan algoritma hash tertentu. finality_threshold adalah jumlah blok
+    // penerus yang produsennya dihitung sebagai dukungan finalitas; 0 dinaikkan menjadi 1 karena jendela kosong
+    // berarti blok hanya bisa final lewat atestasi eksplisit
+    pub fn with_hash_algorithm(finality_threshold: u64, hash_algorithm: HashKind) -> Self {
+        Blockchain {
+            blocks: Vec::new(),
+            hash_index: HashMap::new(),
+            side_blocks: HashMap::new(),
+            reward_records: HashMap::new(),
+            vrf_weights: HashMap::new(),
+            attestations: HashMap::new(),
+            pending_blocks: HashSet::new(),
+            mempool: Vec::new(),
+            max_txs_per_block: DEFAULT_MAX_TXS_PER_BLOCK,
+            max_mempool_size: DEFAULT_MAX_MEMPOOL_SIZE,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            difficulty: 0,
+            max_future_drift_ms: DEFAULT_MAX_FUTURE_DRIFT_MS,
+            validators: HashMap::new(),
+            token_holders: HashMap::new(),
+            accounts: HashMap::new(),
+            account_nonces: HashMap::new(),
+            current_period: 0,
+            periods_since_last_block: 0,
+            stall_threshold: 0,
+            validator_history: Vec::new(),
+            history_window: DEFAULT_HISTORY_WINDOW,
+            finality_threshold: finality_threshold.max(1),
+            justified_height: None,
+            finalized_height: None,
+            vote_tallies: BTreeMap::new(),
+            stake_bases: BTreeMap::new(),
+            min_stake: 0,
+            max_active_validators: 0,
+            epoch_active_set: None,
+            selection_strategy: SelectionStrategy::default(),
+            params: ChainParams::default(),
+            halving_interval: 0,
+            total_supply: 0,
+            supply_baseline: 0,
+            burn_penalties: true,
+            treasury: 0,
+            pending_exits: Vec::new(),
+            seed: None,
+            thread_rng_selection: false,
+            hash_algorithm,
+            security_measures: SecurityMeasures {
+                malicious_activity_log: HashSet::new(),
+                validator_penalties: HashMap::new(),
+                reported_double_signs: HashSet::new(),
+            },
+            signing_keys: HashMap::new(),
+            vrf_keys: HashMap::new(),
+            clock: Arc::new(SystemClock),
+            listeners: Vec::new(),
+            proposer_selector: Arc::new(WeightedRandomSelector),
+        }
+    }
+
+    // Fungsi untuk membuat snapshot blockchain yang dapat diserialisasi
+    fn to_snapshot(&self) -> ChainSnapshot {
+        ChainSnapshot {
+            blocks: self.blocks.clone(),
+            reward_records: self.reward_records.clone(),
+            vrf_weights: self.vrf_weights.clone(),
+            attestations: self.attestations.clone(),
+            max_txs_per_block: self.max_txs_per_block,
+            max_mempool_size: self.max_mempool_size,
+            max_block_size: self.max_block_size,
+            difficulty: self.difficulty,
+            max_future_drift_ms: self.max_future_drift_ms,
+            validators: self.validators.values().cloned().collect(),
+            token_holders: self.token_holders.values().cloned().collect(),
+            accounts: self.accounts.clone(),
+            account_nonces: self.account_nonces.clone(),
+            current_period: self.current_period,
+            periods_since_last_block: self.periods_since_last_block,
+            stall_threshold: self.stall_threshold,
+            validator_history: self.validator_history.clone(),
+            history_window: self.history_window,
+            finality_threshold: self.finality_threshold,
+            justified_height: self.justified_height,
+            finalized_height: self.finalized_height,
+            stake_bases: self.stake_bases.clone(),
+            min_stake: self.min_stake,
+            max_active_validators: self.max_active_validators,
+            epoch_active_set: self.epoch_active_set.clone(),
+            selection_strategy: self.selection_strategy,
+            params: self.params.clone(),
+            halving_interval: self.halving_interval,
+            total_supply: self.total_supply,
+            supply_baseline: self.supply_baseline,
+            burn_penalties: self.burn_penalties,
+            treasury: self.treasury,
+            pending_exits: self.pending_exits.clone(),
+            seed: self.seed,
+            thread_rng_selection: self.thread_rng_selection,
+            hash_algorithm: self.hash_algorithm,
+            security_measures: self.security_measures.clone(),
+        }
+    }
+
+    // Fungsi untuk membangun kembali blockchain dari snapshot
+    fn from_snapshot(snapshot: ChainSnapshot) -> Self {
+        let mut blockchain = Blockchain::with_hash_algorithm(snapshot.finality_threshold, snapshot.hash_algorithm);
+        blockchain.hash_index = snapshot
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (block.hash, index))
+            .collect();
+        blockchain.blocks = snapshot.blocks;
+        blockchain.reward_records = snapshot.reward_records;
+        blockchain.vrf_weights = snapshot.vrf_weights;
+        blockchain.attestations = snapshot.attestations;
+        blockchain.max_txs_per_block = snapshot.max_txs_per_block;
+        blockchain.max_mempool_size = snapshot.max_mempool_size;
+        blockchain.max_block_size = snapshot.max_block_size;
+        blockchain.difficulty = snapshot.difficulty;
+        blockchain.max_future_drift_ms = snapshot.max_future_drift_ms;
+        blockchain.validators = snapshot
+            .validators
+            .into_iter()
+            .map(|validator| (validator.id.clone(), validator))
+            .collect();
+        blockchain.accounts = snapshot.accounts;
+        blockchain.account_nonces = snapshot.account_nonces;
+        blockchain.token_holders = snapshot
+            .token_holders
+            .into_iter()
+            .map(|holder| (holder.id.clone(), holder))
+            .collect();
+        blockchain.current_period = snapshot.current_period;
+        blockchain.periods_since_last_block = snapshot.periods_since_last_block;
+        blockchain.stall_threshold = snapshot.stall_threshold;
+        blockchain.validator_history = snapshot.validator_history;
+        blockchain.history_window = snapshot.history_window;
+        blockchain.justified_height = snapshot.justified_height;
+        blockchain.finalized_height = snapshot.finalized_height;
+        blockchain.stake_bases = snapshot.stake_bases;
+        blockchain.min_stake = snapshot.min_stake;
+        blockchain.max_active_validators = snapshot.max_active_validators;
+        blockchain.epoch_active_set = snapshot.epoch_active_set;
+        blockchain.selection_strategy = snapshot.selection_strategy;
+        blockchain.params = snapshot.params;
+        blockchain.halving_interval = snapshot.halving_interval;
+        blockchain.total_supply = snapshot.total_supply;
+        blockchain.supply_baseline = snapshot.supply_baseline;
+        blockchain.burn_penalties = snapshot.burn_penalties;
+        blockchain.treasury = snapshot.treasury;
+        blockchain.pending_exits = snapshot.pending_exits;
+        blockchain.seed = snapshot.seed;
+        blockchain.thread_rng_selection = snapshot.thread_rng_selection;
+        blockchain.security_measures = snapshot.security_measures;
+        // Snapshot lama tidak menyimpan basis stake, jadi blok yang belum final memakai stake saat dimuat
+        let basis = blockchain.stake_basis();
+        let first_unfinalized = blockchain.finalized_height.map_or(0, |height| height + 1);
+        let unfinalized: Vec<u64> = blockchain.blocks_in_range(first_unfinalized, u64::MAX).map(|block| block.id).collect();
+        for block_id in unfinalized {
+            blockchain.stake_bases.entry(block_id).or_insert_with(|| basis.clone());
+        }
+        // Penghitung suara tidak disimpan dan disusun ulang dari blok serta atestasi yang belum final
+        blockchain.check_finality();
+        blockchain
+    }
+
+    // Fungsi untuk menyimpan blockchain ke file JSON
+    pub fn save_to_json(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.to_snapshot())?;
+        fs::write(path, json)
+    }
+
+    // Fungsi untuk memuat blockchain dari file JSON dan menolak chain yang rusak
+    pub fn load_from_json(path: &Path) -> io::Result<Blockchain> {
+        let snapshot: ChainSnapshot = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Blockchain::from_validated_snapshot(snapshot)
+    }
+
+    // Fungsi untuk menyimpan blockchain ke file biner bincode; untuk chain 10.000 blok hasilnya
+    // sekitar separuh ukuran JSON (±4,2 MB dibanding ±8,0 MB)
+    pub fn save_to_bincode(&self, path: &Path) -> io::Result<()> {
+        let bytes = bincode::serialize(&self.to_snapshot()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(path, bytes)
+    }
+
+    // Fungsi untuk memuat blockchain dari file bincode dan menolak chain yang rusak
+    pub fn load_from_bincode(path: &Path) -> io::Result<Blockchain> {
+        let snapshot: ChainSnapshot =
+            bincode::deserialize(&fs::read(path)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Blockchain::from_validated_snapshot(snapshot)
+    }
+
+    // Fungsi untuk membuat checkpoint pada blok final terakhir, None bila belum ada blok final; efek reward,
+    // fee, dan transfer blok yang belum final dibatalkan, sedangkan efek per periode tetap seperti saat ini
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        let height = self.finalized_height?;
+        let mut state = Blockchain::from_snapshot(self.to_snapshot());
+        let keep = state.blocks.partition_point(|block| block.id <= height);
+        state.truncate_main_chain(keep);
+        state.recompute_delegated_stake();
+        Some(Checkpoint { height, snapshot: state.to_snapshot() })
+    }
+
+    // Fungsi untuk memulai node baru dari checkpoint tepercaya alih-alih dari genesis
+    pub fn restore_from_checkpoint(checkpoint: Checkpoint) -> io::Result<Blockchain> {
+        Blockchain::from_validated_snapshot(checkpoint.snapshot)
+    }
+
+    // Fungsi untuk membangun kembali blockchain dari snapshot yang dimuat dan memeriksa integritasnya
+    fn from_validated_snapshot(snapshot: ChainSnapshot) -> io::Result<Blockchain> {
+        let blockchain = Blockchain::from_snapshot(snapshot);
+        blockchain.validate_chain().map_err(|index| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("corrupted block at index {}", index))
+        })?;
+        Ok(blockchain)
+    }
+
+    // Fungsi untuk mendapatkan tinggi chain, yaitu jumlah blok yang sudah divalidasi termasuk yang sudah di-prune
+    pub fn height(&self) -> u64 {
+        self.blocks.last().map_or(0, |tip| tip.id + 1)
+    }
+
+    // Fungsi untuk mendapatkan seluruh blok di chain
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    // Fungsi untuk mendapatkan blok dengan id dalam rentang [start, end); id di chain
+    // selalu naik sehingga batasnya dicari dengan binary search, bukan indeks langsung
+    pub fn blocks_in_range(&self, start: u64, end: u64) -> impl Iterator<Item = &Block> {
+        let lower = self.blocks.partition_point(|block| block.id < start);
+        let upper = self.blocks.partition_point(|block| block.id < end).max(lower);
+        self.blocks[lower..upper].iter()
+    }
+
+    // Fungsi untuk mencari blok di chain utama berdasarkan id-nya
+    pub fn block_by_id(&self, id: u64) -> Option<&Block> {
+        self.blocks_in_range(id, id.saturating_add(1)).next()
+    }
+
+    // Fungsi untuk mencari blok di chain utama berdasarkan hash-nya
+    pub fn block_by_hash(&self, hash: &Hash) -> Option<&Block> {
+        self.hash_index.get(hash).map(|&index| &self.blocks[index])
+    }
+
+    // Fungsi untuk mendapatkan blok terakhir di chain
+    pub fn latest_block(&self) -> Option<&Block> {
+        self.blocks.last()
+    }
+
+    // Fungsi untuk mendapatkan validator berdasarkan id
+    pub fn validator(&self, id: &str) -> Option<&Validator> {
+        self.validators.get(id)
+    }
+
+    // Fungsi untuk mendapatkan token holder berdasarkan id
+    pub fn token_holder(&self, id: &str) -> Option<&TokenHolder> {
+        self.token_holders.get(id)
+    }
+
+    // Fungsi untuk mendapatkan token holder yang mendelegasikan stake ke validator tertentu, diurutkan berdasarkan id
+    pub fn delegators_of(&self, validator_id: &str) -> Vec<&TokenHolder> {
+        let mut delegators: Vec<&TokenHolder> = self
+            .token_holders
+            .values()
+            .filter(|holder| holder.delegated_to.iter().any(|(delegate, _)| delegate == validator_id))
+            .collect();
+        delegators.sort_by(|a, b| a.id.cmp(&b.id));
+        delegators
+    }
+
+    // Fungsi untuk menjumlahkan seluruh stake yang didelegasikan token holder
+    pub fn total_delegated(&self) -> u64 {
+        self.token_holders
+            .values()
+            .flat_map(|holder| holder.delegated_to.iter().map(|(_, amount)| amount))
+            .sum()
+    }
+
+    // Fungsi untuk menjumlahkan stake milik seluruh validator (tanpa stake delegasi)
+    pub fn total_stake(&self) -> u64 {
+        self.validators.values().map(|validator| validator.stake).sum()
+    }
+
+    // Fungsi untuk membuat ringkasan statistik chain tanpa mengubah state
+    pub fn stats(&self) -> ChainStats {
+        let jailed_validators = self.validators.values().filter(|validator| validator.jailed).count();
+        let active_validators = self
+            .validators
+            .values()
+            .filter(|validator| !validator.jailed)
+            .filter(|validator| !self.security_measures.malicious_activity_log.contains(&validator.id))
+            .count();
+        let average_reputation = if self.validators.is_empty() {
+            0.0
+        } else {
+            self.validators.values().map(|validator| validator.reputation).sum::<f64>() / self.validators.len() as f64
+        };
+        ChainStats {
+            total_blocks: self.height(),
+            active_validators,
+            jailed_validators,
+            total_stake: self.total_stake(),
+            total_delegated_stake: self.validators.values().map(|validator| validator.delegated_stake).sum(),
+            average_reputation,
+            justified_height: self.justified_height,
+            finalized_height: self.finalized_height,
+        }
+    }
+
+    // Fungsi untuk mendapatkan ringkasan ujung chain tanpa menelusuri validator maupun blok
+    pub fn head_info(&self) -> HeadInfo {
+        let head = self.blocks.last();
+        HeadInfo {
+            head_id: head.map(|block| block.id),
+            head_hash: head.map(|block| block.hash),
+            justified_height: self.justified_height,
+            finalized_height: self.finalized_height,
+            total_blocks: self.height(),
+            stalled: self.stall_threshold > 0 && self.is_stalled(self.stall_threshold),
+        }
+    }
+
+    // Fungsi untuk menjawab Query tanpa mengubah state chain
+    pub fn handle_query(&self, query: Query) -> QueryResponse {
+        match query {
+            Query::GetBlock { id } => QueryResponse::Block(self.block_by_id(id).cloned()),
+            Query::GetValidator { id } => QueryResponse::Validator(self.validator(&id).cloned()),
+            Query::GetHead => QueryResponse::Head(self.head_info()),
+            Query::GetStats => QueryResponse::Stats(self.stats()),
+            Query::ListValidators => {
+                let mut validators: Vec<Validator> = self.validators.values().cloned().collect();
+                validators.sort_by(|a, b| a.id.cmp(&b.id));
+                QueryResponse::Validators(validators)
+            }
+        }
+    }
+
+    // Fungsi untuk mengatur halving reward setiap `blocks` blok; 0 menonaktifkan halving
+    pub fn set_halving_interval(&mut self, blocks: u64) {
+        self.halving_interval = blocks;
+    }
+
+    // Fungsi untuk menghitung reward blok saat ini setelah memperhitungkan halving
+    pub fn current_block_reward(&self) -> u64 {
+        if self.halving_interval == 0 {
+            return self.params.block_reward;
+        }
+        let halvings = self.height() / self.halving_interval;
+        u32::try_from(halvings)
+            .ok()
+            .and_then(|halvings| self.params.block_reward.checked_shr(halvings))
+            .unwrap_or(0)
+    }
+
+    // Fungsi untuk mengatur stake minimum agar validator dapat dipilih
+    pub fn set_min_stake(&mut self, min_stake: u64) {
+        self.min_stake = min_stake;
+    }
+
+    // Fungsi untuk membatasi porsi bobot pemilihan satu validator (0.0 - 1.0); 1.0 menonaktifkan batas
+    pub fn set_max_weight_fraction(&mut self, fraction: f64) {
+        self.params.max_weight_fraction = fraction.clamp(0.0, 1.0);
+    }
+
+    // Fungsi untuk mengatur bobot stake delegasi relatif terhadap stake sendiri dalam pemilihan; di bawah 1.0
+    // validator dengan stake sendiri lebih besar diuntungkan, nilai negatif dianggap 0
+    pub fn set_delegated_stake_multiplier(&mut self, multiplier: f64) {
+        self.params.delegated_stake_multiplier = multiplier.max(0.0);
+    }
+
+    // Fungsi untuk membatasi jumlah validator di active set; 0 menonaktifkan batas
+    pub fn set_max_active_validators(&mut self, max_active_validators: usize) {
+        self.max_active_validators = max_active_validators;
+    }
+
+    // Fungsi untuk mengatur strategi pemilihan produsen blok
+    pub fn set_selection_strategy(&mut self, strategy: SelectionStrategy) {
+        self.selection_strategy = strategy;
+    }
+
+    // Fungsi untuk memasang algoritma pemilihan produsen blok sendiri dan beralih ke SelectionStrategy::Custom.
+    // Pemilih tidak ikut disimpan ke disk; chain yang dimuat kembali memakai WeightedRandomSelector sampai
+    // pemilihnya dipasang lagi
+    pub fn set_proposer_selector(&mut self, selector: Box<dyn ProposerSelector>) {
+        self.proposer_selector = Arc::from(selector);
+        self.selection_strategy = SelectionStrategy::Custom;
+    }
+
+    // Fungsi untuk memilih validator berdasarkan stake dan reputasi; secara default RNG di-seed dari state chain
+    // sehingga setiap node dengan state yang sama memilih produsen yang sama
+    pub fn select_validator(&self) -> Option<&Validator> {
+        if self.thread_rng_selection {
+            return self.choose_validator(thread_rng().gen());
+        }
+        self.select_validator_seeded(self.selection_seed())
+    }
+
+    // Fungsi untuk menurunkan seed pemilihan dari hash blok terakhir, dicampur seed simulasi jika diatur
+    pub fn selection_seed(&self) -> u64 {
+        let previous_hash = self.blocks.last().map(|tip| tip.hash).unwrap_or_default();
+        hash_to_u64(previous_hash) ^ self.seed.unwrap_or(0)
+    }
+
+    // Fungsi untuk mendapatkan nilai acak publik (random beacon) yang diturunkan dari hash blok tertentu,
+    // dapat dipakai aplikasi seperti undian; None untuk blok yang tidak ada di chain utama
+    pub fn randomness_at(&self, block_id: u64) -> Option<u64> {
+        self.block_by_id(block_id).map(|block| hash_to_u64(block.hash))
+    }
+
+    // Fungsi untuk memakai thread_rng yang tidak dapat direproduksi saat memilih validator (opt-in, bukan untuk konsensus)
+    pub fn set_thread_rng_selection(&mut self, enabled: bool) {
+        self.thread_rng_selection = enabled;
+    }
+
+    // Fungsi untuk memilih validator dengan RNG ber-seed agar simulasi dapat diulang
+    pub fn select_validator_seeded(&self, seed: u64) -> Option<&Validator> {
+        self.choose_validator(seed)
+    }
+
+    // Fungsi untuk mengecek apakah validator boleh dipilih sebagai produsen blok
+    fn is_eligible(&self, validator: &Validator) -> bool {
+        !self.security_measures.malicious_activity_log.contains(&validator.id)
+            && !validator.jailed
+            && validator.stake + validator.delegated_stake >= self.min_stake
+            && validator.reputation > 0.0
+            && validator.activation_period <= self.current_period
+    }
+
+    // Fungsi untuk memilih validator sesuai strategi melalui ProposerSelector; VRF ditangani terpisah karena
+    // pemenangnya harus dapat dibuktikan dengan kunci VRF validator
+    fn choose_validator(&self, seed: u64) -> Option<&Validator> {
+        let selector: &dyn ProposerSelector = match self.selection_strategy {
+            SelectionStrategy::WeightedRandom => &WeightedRandomSelector,
+            SelectionStrategy::RoundRobin => &RoundRobinSelector,
+            SelectionStrategy::Custom => self.proposer_selector.as_ref(),
+            SelectionStrategy::Vrf => {
+                return self
+                    .vrf_lottery()
+                    .and_then(|(_, validator_id, _, _)| self.validators.get(&validator_id));
+            }
+        };
+        let active_set = self.active_set();
+        let weights = self.capped_weights(&active_set);
+        let ctx = SelectionContext { height: self.height(), seed, weights: &weights };
+        // Referensi hasil pemilih terikat pada active set lokal, jadi validator dicari ulang di chain
+        selector
+            .select(&active_set, &ctx)
+            .and_then(|validator| self.validators.get(&validator.id))
+    }
+
+    // Fungsi untuk mengambil active set. Setelah blok pertama epoch berjalan diterapkan, keanggotaannya tetap
+    // mengikuti set yang dibekukan sampai batas epoch berikutnya; anggota yang sejak itu kehilangan kelayakan
+    // (misalnya di-slash) tetap dikeluarkan
+    pub fn active_set(&self) -> Vec<&Validator> {
+        match self.frozen_active_set() {
+            Some(ids) => ids
+                .iter()
+                .filter_map(|id| self.validators.get(id))
+                .filter(|validator| self.is_eligible(validator))
+                .collect(),
+            None => self.compute_active_set(),
+        }
+    }
+
+    // Fungsi untuk mendapatkan id active set yang sudah dibekukan untuk epoch berjalan, jika ada
+    fn frozen_active_set(&self) -> Option<&[String]> {
+        self.epoch_active_set
+            .as_ref()
+            .filter(|(epoch, _)| *epoch == self.current_epoch())
+            .map(|(_, ids)| ids.as_slice())
+    }
+
+    // Fungsi untuk membekukan active set epoch berjalan bila belum dibekukan
+    fn freeze_active_set(&mut self) {
+        if self.frozen_active_set().is_some() {
+            return;
+        }
+        let ids = self.compute_active_set().into_iter().map(|validator| validator.id.clone()).collect();
+        self.epoch_active_set = Some((self.current_epoch(), ids));
+    }
+
+    // Fungsi untuk menghitung active set dari state sekarang: validator yang memenuhi syarat dengan bobot efektif
+    // tertinggi sebanyak max_active_validators (sisanya standby), diurutkan berdasarkan id karena urutan HashMap
+    // tidak deterministik
+    fn compute_active_set(&self) -> Vec<&Validator> {
+        // Kapasitas dipesan di muka karena collect dari filter tidak tahu ukuran akhirnya dan akan realokasi berulang
+        let mut validators: Vec<&Validator> = Vec::with_capacity(self.validators.len());
+        validators.extend(self.validators.values().filter(|validator| self.is_eligible(validator)));
+        if self.max_active_validators > 0 && validators.len() > self.max_active_validators {
+            validators.sort_by(|a, b| {
+                self.selection_weight(b)
+                    .total_cmp(&self.selection_weight(a))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+            validators.truncate(self.max_active_validators);
+        }
+        validators.sort_by(|a, b| a.id.cmp(&b.id));
+        validators
+    }
+
+    // Fungsi untuk menghitung bobot efektif validator dalam pemilihan; stake delegasi dihitung dengan
+    // delegated_stake_multiplier relatif terhadap stake sendiri
+    fn selection_weight(&self, validator: &Validator) -> f64 {
+        let delegated = validator.delegated_stake as f64 * self.params.delegated_stake_multiplier;
+        (validator.stake as f64 + delegated) * validator.reputation
+    }
+
+    // Fungsi untuk menghitung bobot pemilihan setelah dibatasi max_weight_fraction: bobot di atas batas c dipotong
+    // menjadi c, dengan c = fraksi * total bobot setelah pemotongan (dicari berulang karena pemotongan mengecilkan total)
+    fn capped_weights(&self, validators: &[&Validator]) -> Vec<f64> {
+        let mut weights: Vec<f64> = validators.iter().map(|validator| self.selection_weight(validator)).collect();
+        let fraction = self.params.max_weight_fraction;
+        if fraction >= 1.0 || weights.is_empty() {
+            return weights;
+        }
+        let mut capped = vec![false; weights.len()];
+        loop {
+            let uncapped_total: f64 = weights
+                .iter()
+                .zip(&capped)
+                .filter(|&(_, &is_capped)| !is_capped)
+                .map(|(weight, _)| weight)
+                .sum();
+            let capped_count = capped.iter().filter(|&&is_capped| is_capped).count();
+            let denominator = 1.0 - fraction * capped_count as f64;
+            // Batas tidak dapat dipenuhi (fraksi terlalu kecil untuk jumlah validator), jadi bobot disamaratakan
+            if capped_count == weights.len() || denominator <= 0.0 {
+                return weights.iter().map(|&weight| if weight > 0.0 { 1.0 } else { 0.0 }).collect();
+            }
+            let cap = fraction * uncapped_total / denominator;
+            let mut changed = false;
+            for (weight, is_capped) in weights.iter().zip(capped.iter_mut()) {
+                if !*is_capped && *weight > cap {
+                    *is_capped = true;
+                    changed = true;
+                }
+            }
+            if !changed {
+                for (weight, is_capped) in weights.iter_mut().zip(&capped) {
+                    if *is_capped {
+                        *weight = cap;
+                    }
+                }
+                return weights;
+            }
+        }
+    }
+
+    // Fungsi untuk menghitung peluang setiap validator yang memenuhi syarat terpilih oleh select_validator
+    pub fn selection_probabilities(&self) -> HashMap<String, f64> {
+        let validators = self.active_set();
+        let weights = self.capped_weights(&validators);
+        let total_weight: f64 = weights.iter().sum();
+        if total_weight <= 0.0 {
+            return HashMap::new();
+        }
+        validators
+            .into_iter()
+            .zip(weights)
+            .map(|(validator, weight)| (validator.id.clone(), weight / total_weight))
+            .collect()
+    }
+
+    // Fungsi untuk menghitung uptime validator: blok yang diproduksi dibagi total blok yang diproduksi dan terlewat;
+    // None jika validator tidak dikenal atau belum memiliki catatan sama sekali
+    pub fn validator_uptime(&self, id: &str) -> Option<f64> {
+        let validator = self.validators.get(id)?;
+        let total = validator.blocks_produced + validator.blocks_missed;
+        if total == 0 {
+            return None;
+        }
+        Some(validator.blocks_produced as f64 / total as f64)
+    }
+
+    // Fungsi untuk mengurutkan validator aktif berdasarkan bobot efektif (terbesar dahulu),
+    // validator dengan bobot sama diurutkan berdasarkan id
+    pub fn leaderboard(&self) -> Vec<(String, f64)> {
+        let mut ranking: Vec<(String, f64)> = self
+            .validators
+            .values()
+            .filter(|validator| !validator.jailed && !self.security_measures.malicious_activity_log.contains(&validator.id))
+            .map(|validator| (validator.id.clone(), self.selection_weight(validator)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking
+    }
+
+    // Fungsi untuk mengatur seed yang dicampurkan ke seed pemilihan validator
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    // Fungsi untuk mengecek apakah blok akan diterima validate_block tanpa mengubah state atau memberi reward
+    pub fn would_accept(&self, block: &Block) -> Result<(), HybridStakeError> {
+        Ok(self.check_block(block)?)
+    }
+
+    // Fungsi untuk memeriksa blok terhadap ujung chain tanpa mengubah state
+    fn check_block(&self, block: &Block) -> Result<(), ValidationError> {
+        if self.blocks.iter().any(|existing| existing.id == block.id) {
+            return Err(ValidationError::DuplicateBlockId(block.id));
+        }
+        // Chain harus naik tepat satu per blok tanpa celah
+        let expected_id = self.blocks.last().map_or(0, |tip| tip.id + 1);
+        if block.id != expected_id {
+            return Err(ValidationError::UnexpectedBlockId { expected: expected_id, found: block.id });
+        }
+        let tip_hash = self.blocks.last().map(|tip| tip.hash).unwrap_or_default();
+        if block.previous_hash != tip_hash {
+            return Err(ValidationError::BadParentHash);
+        }
+        // Hash dan Merkle root dihitung ulang karena blok dari luar dapat diubah setelah di-hash
+        if !block.verify_hash() {
+            return Err(ValidationError::BadHash);
+        }
+        self.check_timestamp(block, self.blocks.last().map(|tip| tip.timestamp))?;
+        let size = block.payload_size();
+        if size > self.max_block_size {
+            return Err(ValidationError::BlockTooLarge { size, max: self.max_block_size });
+        }
+        if !block.meets_difficulty(self.difficulty) {
+            return Err(ValidationError::InsufficientWork);
+        }
+        self.verify_block_signature(block)?;
+        self.verify_vrf(block)?;
+        self.apply_transactions(&block.transactions).map(|_| ())
+    }
+
+    // Fungsi untuk menerapkan transaksi secara berurutan pada salinan saldo dan nonce akun yang disentuh;
+    // satu transaksi gagal membuat seluruh blok tidak valid
+    fn apply_transactions(&self, transactions: &[Transaction]) -> Result<AccountChanges, ValidationError> {
+        let mut changes = AccountChanges::default();
+        for tx in transactions {
+            self.apply_transaction(&mut changes, tx).map_err(ValidationError::InvalidTransaction)?;
+        }
+        Ok(changes)
+    }
+
+    // Fungsi untuk memeriksa nonce pengirim, lalu mendebit pengirim (jumlah + fee) dan mengkredit penerima
+    // pada perubahan sementara; transaksi memo tanpa pengirim tidak memindahkan apa pun, termasuk fee-nya
+    fn apply_transaction(&self, changes: &mut AccountChanges, tx: &Transaction) -> Result<(), TransactionError> {
+        if tx.from.is_empty() {
+            return Ok(());
+        }
+        let expected = changes.nonces.get(&tx.from).copied().unwrap_or_else(|| self.account_nonce(&tx.from));
+        if tx.nonce != expected {
+            return Err(TransactionError::BadNonce { account: tx.from.clone(), expected, found: tx.nonce });
+        }
+        let balance = changes.balances.get(&tx.from).copied().unwrap_or_else(|| self.balance(&tx.from));
+        let required = tx.amount.saturating_add(tx.fee);
+        if balance < required {
+            return Err(TransactionError::InsufficientBalance { account: tx.from.clone(), balance, required });
+        }
+        changes.fees = changes.fees.checked_add(tx.fee).ok_or(TransactionError::FeeOverflow)?;
+        changes.nonces.insert(tx.from.clone(), expected + 1);
+        changes.balances.insert(tx.from.clone(), balance - required);
+        let recipient = changes.balances.get(&tx.to).copied().unwrap_or_else(|| self.balance(&tx.to));
+        changes.balances.insert(tx.to.clone(), recipient + tx.amount);
+        Ok(())
+    }
+
+    // Fungsi untuk membatalkan perpindahan saldo dan kenaikan nonce transaksi sebuah blok, dalam urutan terbalik
+    fn revert_transactions(&mut self, transactions: &[Transaction]) {
+        for tx in transactions.iter().rev().filter(|tx| !tx.from.is_empty()) {
+            let recipient = self.accounts.entry(tx.to.clone()).or_insert(0);
+            *recipient = recipient.saturating_sub(tx.amount);
+            *self.accounts.entry(tx.from.clone()).or_insert(0) += tx.amount + tx.fee;
+            if let Some(nonce) = self.account_nonces.get_mut(&tx.from) {
+                *nonce = nonce.saturating_sub(1);
+            }
+        }
+    }
+
+    // Fungsi untuk mendapatkan nonce yang wajib dipakai transaksi berikutnya dari sebuah akun
+    pub fn account_nonce(&self, account: &str) -> u64 {
+        self.account_nonces.get(account).copied().unwrap_or(0)
+    }
+
+    // Fungsi untuk memeriksa apakah nonce transaksi sudah terpakai sehingga tidak akan pernah valid lagi
+    fn is_stale(&self, tx: &Transaction) -> bool {
+        !tx.from.is_empty() && tx.nonce < self.account_nonce(&tx.from)
+    }
+
+    // Fungsi untuk mendapatkan saldo akun; akun yang belum pernah tercatat bersaldo 0
+    pub fn balance(&self, account: &str) -> u64 {
+        self.accounts.get(account).copied().unwrap_or(0)
+    }
+
+    // Fungsi untuk mengisi saldo awal akun; dicatat sebagai alokasi, bukan inflasi
+    pub fn fund_account(&mut self, account: &str, amount: u64) {
+        *self.accounts.entry(account.to_string()).or_insert(0) += amount;
+        self.allocate_supply(amount);
+    }
+
+    // Fungsi untuk memvalidasi dan menambahkan blok ke dalam blockchain
+    pub fn validate_block(&mut self, block: Block) -> Result<(), HybridStakeError> {
+        Ok(self.accept_block(block)?)
+    }
+
+    // Fungsi untuk memeriksa blok lalu menerapkannya ke chain
+    fn accept_block(&mut self, block: Block) -> Result<(), ValidationError> {
+        self.check_block(&block)?;
+        self.commit_block(block)
+    }
+
+    // Fungsi untuk menerapkan blok ke chain tanpa pemeriksaan would_accept: transaksi, reward, fee, dan
+    // finalitas. Dipakai validate_block setelah blok lolos validasi, dan oleh reorg untuk mengembalikan blok
+    // chain utama yang dulu sudah divalidasi walaupun aturan seperti batas ukuran blok telah berubah
+    fn commit_block(&mut self, block: Block) -> Result<(), ValidationError> {
+        if !self.validators.contains_key(&block.validator_id) {
+            return Err(ValidationError::UnknownValidator(block.validator_id.clone()));
+        }
+        // Active set yang dipakai untuk memvalidasi blok pertama epoch ini berlaku sampai batas epoch berikutnya
+        self.freeze_active_set();
+        let changes = self.apply_transactions(&block.transactions)?;
+        // Hanya fee yang benar-benar didebit dari pengirim yang dikreditkan ke produsen blok
+        let fees = changes.fees;
+        self.accounts.extend(changes.balances);
+        self.account_nonces.extend(changes.nonces);
+        let validator = self
+            .validators
+            .get_mut(&block.validator_id)
+            .expect("block producer was checked above");
+        let previous_reputation = validator.reputation;
+        let previous_last_block_validated = validator.last_block_validated;
+        validator.last_block_validated = self.current_period;
+        validator.missed_periods = 0;
+        validator.blocks_produced += 1;
+        validator.reputation = (validator.reputation + self.params.reputation_gain).clamp(0.0, MAX_REPUTATION); // Increase reputation
+        self.periods_since_last_block = 0;
+        let reward = self.current_block_reward();
+        let (validator_reward, delegator_rewards) = self.distribute_reward(&block.validator_id, reward); // Reward
+        self.total_supply += reward;
+        // Fee adalah perpindahan dari pengirim transaksi ke produsen blok, bukan pencetakan baru,
+        // sehingga total_supply tidak bertambah
+        if let Some(validator) = self.validators.get_mut(&block.validator_id) {
+            validator.stake += fees;
+        }
+        debug!("Block #{} by {} validated, reward {} plus {} in fees", block.id, block.validator_id, reward, fees);
+        self.emit(ChainEvent::BlockValidated {
+            block_id: block.id,
+            validator_id: block.validator_id.clone(),
+            reward,
+        });
+        self.reward_records.insert(
+            block.hash,
+            RewardRecord {
+                validator_id: block.validator_id.clone(),
+                validator_reward,
+                delegator_rewards,
+                previous_reputation,
+                previous_last_block_validated,
+                fees,
+            },
+        );
+        // Bobot undian anak blok ini dibekukan sekarang agar verifikasinya tidak bergantung pada periode penerima
+        if self.selection_strategy == SelectionStrategy::Vrf {
+            self.vrf_weights.insert(block.hash, self.current_vrf_weights());
+        }
+        self.pending_blocks.remove(&block); // Hanya blok yang divalidasi yang keluar dari antrean
+        // Transaksi yang nonce-nya sudah terpakai (replay) ikut dibuang dari mempool
+        let mempool = std::mem::take(&mut self.mempool);
+        self.mempool = mempool.into_iter().filter(|tx| !block.transactions.contains(tx) && !self.is_stale(tx)).collect();
+        self.hash_index.insert(block.hash, self.blocks.len());
+        let basis = self.stake_basis();
+        self.stake_bases.insert(block.id, basis);
+        self.blocks.push(block);
+        let justified = self.record_votes(self.blocks.len() - 1);
+        self.advance_finality(justified);
+        Ok(())
+    }
+
+    // Fungsi untuk memvalidasi dan menambahkan sekumpulan blok (misalnya hasil sinkronisasi dari peer) sesuai
+    // urutannya. Mengembalikan jumlah blok yang diterima, atau indeks dan error blok pertama yang gagal;
+    // blok sebelum kegagalan tetap tersimpan di chain
+    pub fn import_blocks(&mut self, blocks: Vec<Block>) -> Result<usize, (usize, HybridStakeError)> {
+        let count = blocks.len();
+        for (index, block) in blocks.into_iter().enumerate() {
+            self.accept_block(block).map_err(|err| (index, err.into()))?;
+        }
+        Ok(count)
+    }
+
+    // Fungsi untuk menolak blok yang lebih tua dari induknya atau terlalu jauh di masa depan
+    fn check_timestamp(&self, block: &Block, parent_timestamp: Option<u128>) -> Result<(), ValidationError> {
+        let too_old = parent_timestamp.is_some_and(|parent_timestamp| block.timestamp < parent_timestamp);
+        let too_new = block.timestamp > self.clock.now_millis() + self.max_future_drift_ms;
+        if too_old || too_new {
+            return Err(ValidationError::BadTimestamp);
+        }
+        Ok(())
+    }
+
+    // Fungsi untuk memastikan blok ditandatangani oleh validator yang diklaim; blok dari validator tanpa kunci
+    // publik (misalnya dari snapshot lama) selalu ditolak
+    fn verify_block_signature(&self, block: &Block) -> Result<(), ValidationError> {
+        let validator = self
+            .validators
+            .get(&block.validator_id)
+            .ok_or_else(|| ValidationError::UnknownValidator(block.validator_id.clone()))?;
+        match validator.public_key() {
+            Some(public_key) if block.verify_signature(&public_key) => Ok(()),
+            _ => Err(ValidationError::BadSignature),
+        }
+    }
+
+    // Fungsi untuk mendaftarkan kunci publik validator yang kunci privatnya dipegang di luar chain ini;
+    // kunci privat lokal yang lama dibuang karena tidak lagi cocok
+    pub fn set_public_key(&mut self, validator_id: &str, public_key: VerifyingKey) -> Result<(), HybridStakeError> {
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        validator.public_key = Some(public_key.to_bytes());
+        self.signing_keys.remove(validator_id);
+        Ok(())
+    }
+
+    // Fungsi untuk menyimpan kunci privat validator lokal agar blok yang diproduksi simulasi ikut ditandatangani.
+    // Kunci privat hanya disimpan di memori; snapshot hanya memuat kunci publik sehingga setelah dimuat ulang
+    // kuncinya harus didaftarkan lagi
+    pub fn register_signing_key(&mut self, validator_id: &str, signing_key: SigningKey) -> Result<(), HybridStakeError> {
+        self.set_public_key(validator_id, signing_key.verifying_key())?;
+        self.signing_keys.insert(validator_id.to_string(), signing_key);
+        Ok(())
+    }
+
+    // Fungsi untuk menandatangani blok dengan kunci privat lokal produsennya; false jika kuncinya tidak tersedia
+    pub fn sign_block(&self, block: &mut Block) -> bool {
+        match self.signing_keys.get(&block.validator_id) {
+            Some(signing_key) => {
+                block.sign(signing_key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Fungsi untuk menyimpan keypair VRF validator lokal agar dapat mengikuti undian produsen blok
+    pub fn register_vrf_keypair(&mut self, validator_id: &str, keypair: Keypair) -> Result<(), HybridStakeError> {
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        validator.vrf_public_key = Some(keypair.public.to_bytes());
+        self.vrf_keys.insert(validator_id.to_string(), keypair);
+        Ok(())
+    }
+
+    // Fungsi untuk menghitung bobot undian VRF active set dari state sekarang, sebagai (id validator, bobot)
+    fn current_vrf_weights(&self) -> Vec<(String, f64)> {
+        let active_set = self.active_set();
+        let weights = self.capped_weights(&active_set);
+        active_set.into_iter().map(|validator| validator.id.clone()).zip(weights).collect()
+    }
+
+    // Fungsi untuk mengambil bobot undian VRF yang berlaku bagi anak sebuah blok, yaitu bobot yang dibekukan saat blok
+    // itu diterapkan; induk tanpa catatan (blok pertama atau snapshot lama) memakai bobot dari state sekarang
+    fn vrf_weights_after(&self, parent: Hash) -> Vec<(String, f64)> {
+        self.vrf_weights.get(&parent).cloned().unwrap_or_else(|| self.current_vrf_weights())
+    }
+
+    // Fungsi untuk menjalankan undian VRF atas hash blok terakhir dan periode berjalan sebagai slot: setiap validator
+    // dalam bobot undian yang memiliki keypair VRF menghitung output acaknya, dan di antara yang lolos ambang kelayakan
+    // skor -ln(u)/bobot terkecil menang sehingga peluang sebanding bobot. Mengembalikan slot, id pemenang, serta
+    // output dan bukti VRF-nya
+    fn vrf_lottery(&self) -> Option<(u64, String, Vec<u8>, Vec<u8>)> {
+        let previous_hash = self.blocks.last().map(|tip| tip.hash).unwrap_or_default();
+        let slot = self.current_period;
+        let message = vrf_message(previous_hash, slot);
+        let context = signing_context(VRF_CONTEXT);
+        let weights = self.vrf_weights_after(previous_hash);
+        let total_weight: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        weights
+            .into_iter()
+            .filter_map(|(validator_id, weight)| {
+                // Anggota yang sejak itu kehilangan kelayakan (misalnya di-slash) tidak ikut undian
+                self.validators.get(&validator_id).filter(|validator| self.is_eligible(validator))?;
+                let keypair = self.vrf_keys.get(&validator_id)?;
+                let (inout, proof, _) = keypair.vrf_sign(context.bytes(&message));
+                let score = vrf_score(&inout, weight, total_weight)?;
+                Some((score, validator_id, inout.to_preout().to_bytes().to_vec(), proof.to_bytes().to_vec()))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, validator_id, output, proof)| (slot, validator_id, output, proof))
+    }
+
+    // Fungsi untuk memverifikasi bukti VRF produsen blok atas hash blok induk dan slot yang tercatat di blok, serta
+    // bahwa outputnya lolos ambang kelayakan sesuai bobot undian induknya; hanya diwajibkan pada strategi VRF
+    fn verify_vrf(&self, block: &Block) -> Result<(), ValidationError> {
+        if self.selection_strategy != SelectionStrategy::Vrf {
+            return Ok(());
+        }
+        // Slot harus naik dari slot induk agar undian slot yang sudah lewat tidak dapat diulang
+        let parent_slot = self
+            .block_by_hash(&block.previous_hash)
+            .or_else(|| self.side_blocks.get(&block.previous_hash))
+            .map(|parent| parent.slot);
+        if parent_slot.is_some_and(|slot| block.slot <= slot) {
+            return Err(ValidationError::BadVrfProof);
+        }
+        let public_key = self
+            .validators
+            .get(&block.validator_id)
+            .filter(|validator| self.is_eligible(validator))
+            .and_then(|validator| validator.vrf_public_key())
+            .ok_or(ValidationError::BadVrfProof)?;
+        let output = VRFPreOut::from_bytes(&block.vrf_output).map_err(|_| ValidationError::BadVrfProof)?;
+        let proof = VRFProof::from_bytes(&block.vrf_proof).map_err(|_| ValidationError::BadVrfProof)?;
+        let (inout, _) = public_key
+            .vrf_verify(signing_context(VRF_CONTEXT).bytes(&vrf_message(block.previous_hash, block.slot)), &output, &proof)
+            .map_err(|_| ValidationError::BadVrfProof)?;
+        let weights = self.vrf_weights_after(block.previous_hash);
+        let total_weight: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        let weight = weights
+            .iter()
+            .find(|(validator_id, _)| *validator_id == block.validator_id)
+            .map(|&(_, weight)| weight)
+            .ok_or(ValidationError::BadVrfProof)?;
+        vrf_score(&inout, weight, total_weight)
+            .map(|_| ())
+            .ok_or(ValidationError::BadVrfProof)
+    }
+
+    // Fungsi untuk membagi reward antara validator dan delegatornya sesuai proporsi stake,
+    // mengembalikan bagian validator dan bagian setiap delegator
+    fn distribute_reward(&mut self, validator_id: &str, reward: u64) -> (u64, Vec<(String, u64)>) {
+        let validator = match self.validators.get_mut(validator_id) {
+            Some(validator) => validator,
+            None => return (0, Vec::new()),
+        };
+        let delegations: Vec<(String, u64)> = self
+            .token_holders
+            .values()
+            .flat_map(|holder| {
+                holder
+                    .delegated_to
+                    .iter()
+                    .filter(|(delegate, _)| delegate == validator_id)
+                    .map(move |(_, amount)| (holder.id.clone(), *amount))
+            })
+            .collect();
+        let delegated_total: u64 = delegations.iter().map(|(_, amount)| amount).sum();
+        let total_stake = validator.stake + delegated_total;
+        let delegator_pool = if total_stake == 0 {
+            0
+        } else {
+            (reward as u128 * delegated_total as u128 / total_stake as u128) as u64
+        };
+        let commission = (delegator_pool as f64 * validator.commission_rate).round() as u64;
+        let distributable = delegator_pool.saturating_sub(commission);
+
+        let mut distributed = 0;
+        let mut delegator_rewards = Vec::new();
+        for (holder_id, amount) in delegations {
+            let share = (distributable as u128 * amount as u128 / delegated_total as u128) as u64;
+            if let Some(holder) = self.token_holders.get_mut(&holder_id) {
+                holder.stake += share;
+                distributed += share;
+                delegator_rewards.push((holder_id, share));
+            }
+        }
+        // Sisa pembulatan dan komisi menjadi bagian validator
+        let validator_reward = reward - distributed;
+        validator.stake += validator_reward;
+        (validator_reward, delegator_rewards)
+    }
+
+    // Fungsi untuk membatalkan efek reward sebuah blok yang dikeluarkan dari chain utama
+    fn revert_block_effects(&mut self, block: &Block) {
+        self.vrf_weights.remove(&block.hash);
+        let record = match self.reward_records.remove(&block.hash) {
+            Some(record) => record,
+            None => return,
+        };
+        self.revert_transactions(&block.transactions);
+        let minted = record.validator_reward + record.delegator_rewards.iter().map(|(_, share)| share).sum::<u64>();
+        self.total_supply = self.total_supply.saturating_sub(minted);
+        if let Some(validator) = self.validators.get_mut(&record.validator_id) {
+            validator.stake = validator.stake.saturating_sub(record.validator_reward + record.fees);
+            validator.reputation = record.previous_reputation;
+            validator.last_block_validated = record.previous_last_block_validated;
+            validator.blocks_produced = validator.blocks_produced.saturating_sub(1);
+        }
+        for (holder_id, share) in record.delegator_rewards {
+            if let Some(holder) = self.token_holders.get_mut(&holder_id) {
+                holder.stake = holder.stake.saturating_sub(share);
+            }
+        }
+    }
+
+    // Fungsi untuk menerima blok dari jaringan: memperpanjang chain utama, menyimpannya sebagai cabang,
+    // atau melakukan reorg jika cabang tersebut lebih berat secara stake daripada chain utama sejak titik fork
+    pub fn receive_block(&mut self, block: Block) -> Result<ReceiveOutcome, HybridStakeError> {
+        let tip_hash = self.blocks.last().map(|tip| tip.hash).unwrap_or_default();
+        if block.previous_hash == tip_hash {
+            self.accept_block(block)?;
+            return Ok(ReceiveOutcome::Extended);
+        }
+        if !block.verify_hash() {
+            return Err(ValidationError::BadHash.into());
+        }
+        if !block.meets_difficulty(self.difficulty) {
+            return Err(ValidationError::InsufficientWork.into());
+        }
+        self.verify_block_signature(&block)?;
+        self.verify_vrf(&block)?;
+        let (parent_id, parent_timestamp) = self
+            .block_by_hash(&block.previous_hash)
+            .or_else(|| self.side_blocks.get(&block.previous_hash))
+            .map(|parent| (parent.id, parent.timestamp))
+            .ok_or(ValidationError::BadParentHash)?;
+        self.check_timestamp(&block, Some(parent_timestamp))?;
+        if block.id != parent_id + 1 {
+            return Err(ValidationError::UnexpectedBlockId { expected: parent_id + 1, found: block.id }.into());
+        }
+
+        // Telusuri cabang mundur sampai bertemu blok di chain utama
+        let mut branch = Vec::new();
+        let mut cursor = block.hash;
+        self.side_blocks.insert(block.hash, block);
+        let fork_index = loop {
+            if let Some(&index) = self.hash_index.get(&cursor) {
+                break index;
+            }
+            let side = &self.side_blocks[&cursor];
+            branch.push(cursor);
+            cursor = side.previous_hash;
+        };
+        branch.reverse();
+
+        let reverts_finalized = match (self.finalized_height, self.blocks.get(fork_index + 1)) {
+            (Some(height), Some(first_reverted)) => first_reverted.id <= height,
+            _ => false,
+        };
+        let main_weight = self.blocks_weight(&self.blocks[fork_index + 1..]);
+        let branch_weight = self.blocks_weight(branch.iter().map(|hash| &self.side_blocks[hash]));
+        if branch_weight <= main_weight || reverts_finalized {
+            return Ok(ReceiveOutcome::Forked);
+        }
+        self.reorg(fork_index, &branch)?;
+        Ok(ReceiveOutcome::Reorged)
+    }
+
+    // Fungsi untuk menghitung bobot stake chain utama: jumlah stake efektif produsen setiap blok
+    pub fn chain_weight(&self) -> u128 {
+        self.blocks_weight(&self.blocks)
+    }
+
+    // Fungsi untuk menjumlahkan stake efektif (stake sendiri ditambah delegasi) produsen dari sekumpulan blok;
+    // produsen yang tidak dikenal tidak menambah bobot
+    fn blocks_weight<'a>(&self, blocks: impl IntoIterator<Item = &'a Block>) -> u128 {
+        blocks
+            .into_iter()
+            .filter_map(|block| self.validators.get(&block.validator_id))
+            .map(|validator| validator.stake as u128 + validator.delegated_stake as u128)
+            .sum()
+    }
+
+    // Fungsi untuk mengganti blok setelah fork_index dengan blok cabang; jika cabang gagal divalidasi,
+    // blok chain utama dipasang kembali tanpa divalidasi ulang dan error cabang dikembalikan
+    fn reorg(&mut self, fork_index: usize, branch: &[Hash]) -> Result<(), ValidationError> {
+        let removed = self.truncate_main_chain(fork_index + 1);
+        for hash in branch {
+            let block = self.side_blocks.remove(hash).expect("branch blocks are stored as side blocks");
+            if let Err(err) = self.accept_block(block.clone()) {
+                self.side_blocks.insert(block.hash, block);
+                for reverted in self.truncate_main_chain(fork_index + 1) {
+                    self.side_blocks.insert(reverted.hash, reverted);
+                }
+                // Kegagalan memasang kembali blok lama lebih serius daripada error cabang, jadi itu yang dilaporkan
+                let restored = removed.into_iter().try_for_each(|restored| self.commit_block(restored));
+                self.recompute_delegated_stake();
+                return restored.and(Err(err));
+            }
+        }
+        for block in removed {
+            self.side_blocks.insert(block.hash, block);
+        }
+        self.recompute_delegated_stake();
+        Ok(())
+    }
+
+    // Fungsi untuk mengeluarkan blok dari chain utama beserta efek reward-nya sampai tersisa `keep` blok,
+    // mengembalikan blok yang dikeluarkan sesuai urutan chain
+    fn truncate_main_chain(&mut self, keep: usize) -> Vec<Block> {
+        let mut removed = Vec::new();
+        while self.blocks.len() > keep {
+            let block = self.blocks.pop().expect("chain is longer than keep");
+            self.hash_index.remove(&block.hash);
+            self.stake_bases.remove(&block.id);
+            self.revert_block_effects(&block);
+            removed.push(block);
+        }
+        removed.reverse();
+        // Riwayat validator untuk blok yang dikeluarkan tidak lagi berlaku
+        let tip = self.blocks.last().map(|block| block.id);
+        self.validator_history.retain(|(recorded, _)| tip.is_some_and(|tip| *recorded <= tip));
+        // Justifikasi blok yang dikeluarkan ikut batal; yang tersisa minimal setinggi blok final
+        if self.justified_height.is_some_and(|height| self.blocks.last().is_none_or(|tip| height > tip.id)) {
+            self.justified_height = self.finalized_height;
+        }
+        // Suara dari produsen blok yang dikeluarkan juga batal, jadi penghitung disusun ulang
+        self.check_finality();
+        removed
+    }
+
+    // Fungsi untuk membatalkan n blok terakhir beserta efek reward-nya; blok yang sudah final tidak dapat dibatalkan
+    pub fn rollback(&mut self, n: usize) -> Result<Vec<Block>, HybridStakeError> {
+        if n > self.blocks.len() {
+            return Err(ChainError::RollbackTooDeep { requested: n, available: self.blocks.len() }.into());
+        }
+        let keep = self.blocks.len() - n;
+        if let (Some(finalized_height), Some(first_reverted)) = (self.finalized_height, self.blocks.get(keep)) {
+            if first_reverted.id <= finalized_height {
+                return Err(ChainError::WouldRevertFinalized(finalized_height).into());
+            }
+        }
+        let removed = self.truncate_main_chain(keep);
+        self.recompute_delegated_stake();
+        Ok(removed)
+    }
+
+    // Fungsi untuk membuang blok dengan id di bawah `keep_after` dari memori; hanya blok final yang boleh
+    // dibuang sehingga tidak akan pernah dibutuhkan lagi untuk reorg. Blok pertama yang tersisa tetap
+    // menyimpan previous_hash ke blok yang dibuang, dan validate_chain memeriksa tautan mulai dari blok itu.
+    // Mengembalikan jumlah blok yang dibuang
+    pub fn prune(&mut self, keep_after: u64) -> Result<usize, HybridStakeError> {
+        if self.finalized_height.is_none_or(|height| keep_after > height) {
+            return Err(ChainError::PruneBeyondFinalized { keep_after, finalized_height: self.finalized_height }.into());
+        }
+        let cut = self.blocks.partition_point(|block| block.id < keep_after);
+        for block in self.blocks.drain(..cut) {
+            // Blok final tidak dapat dibatalkan sehingga catatan reward dan atestasinya tidak diperlukan lagi
+            self.reward_records.remove(&block.hash);
+            self.vrf_weights.remove(&block.hash);
+            self.attestations.remove(&block.hash);
+        }
+        self.side_blocks.retain(|_, block| block.id >= keep_after);
+        self.hash_index = self
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (block.hash, index))
+            .collect();
+        Ok(cut)
+    }
+
+    // Fungsi untuk memeriksa integritas seluruh blok, mengembalikan indeks blok pertama yang rusak
+    pub fn validate_chain(&self) -> Result<(), usize> {
+        for (index, block) in self.blocks.iter().enumerate() {
+            if !block.verify_hash() {
+                return Err(index);
+            }
+            if index > 0 && block.previous_hash != self.blocks[index - 1].hash {
+                return Err(index);
+            }
+        }
+        Ok(())
+    }
+
+    // Fungsi untuk mengirim transaksi ke mempool agar dimasukkan ke blok berikutnya. Jika mempool penuh,
+    // transaksi ber-fee terendah (yang tertua bila fee sama) dibuang; mengembalikan false jika yang dibuang transaksi ini
+    pub fn submit_transaction(&mut self, tx: Transaction) -> bool {
+        if self.is_stale(&tx) {
+            return false;
+        }
+        self.mempool.push(tx);
+        if self.mempool.len() <= self.max_mempool_size {
+            return true;
+        }
+        self.evict_lowest_fee() != self.mempool.len()
+    }
+
+    // Fungsi untuk membuang transaksi ber-fee terendah (yang tertua bila fee sama), mengembalikan indeksnya
+    fn evict_lowest_fee(&mut self) -> usize {
+        let evicted = self
+            .mempool
+            .iter()
+            .enumerate()
+            .min_by_key(|&(index, tx)| (tx.fee, index))
+            .map(|(index, _)| index)
+            .expect("mempool is not empty");
+        self.mempool.remove(evicted);
+        evicted
+    }
+
+    pub fn mempool_len(&self) -> usize {
+        self.mempool.len()
+    }
+
+    // Fungsi untuk mengatur kapasitas mempool
+    pub fn set_max_mempool_size(&mut self, max_mempool_size: usize) {
+        self.max_mempool_size = max_mempool_size;
+        while self.mempool.len() > self.max_mempool_size {
+            self.evict_lowest_fee();
+        }
+    }
+
+    pub fn mempool(&self) -> &[Transaction] {
+        &self.mempool
+    }
+
+    // Fungsi untuk mengatur jumlah transaksi maksimum per blok
+    pub fn set_max_txs_per_block(&mut self, max_txs_per_block: usize) {
+        self.max_txs_per_block = max_txs_per_block;
+    }
+
+    // Fungsi untuk mengatur ukuran payload transaksi maksimum per blok
+    pub fn set_max_block_size(&mut self, max_block_size: usize) {
+        self.max_block_size = max_block_size;
+    }
+
+    // Fungsi untuk mengganti sumber waktu yang dipakai saat membangun blok
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Arc::from(clock);
+    }
+
+    // Fungsi untuk mengatur seberapa jauh (ms) timestamp blok boleh mendahului waktu lokal
+    pub fn set_max_future_drift_ms(&mut self, max_future_drift_ms: u128) {
+        self.max_future_drift_ms = max_future_drift_ms;
+    }
+
+    // Fungsi untuk mengatur kesulitan proof-of-work; 0 menonaktifkan proof-of-work
+    pub fn set_difficulty(&mut self, difficulty: usize) {
+        self.difficulty = difficulty;
+    }
+
+    // Fungsi untuk menambahkan blok yang menunggu validasi; blok yang bertentangan dengan blok pending
+    // lain dari validator yang sama pada ketinggian yang sama dilaporkan sebagai double sign dan ditolak
+    pub fn add_pending_block(&mut self, block: Block) -> bool {
+        let equivocates = self.pending_blocks.iter().any(|pending| {
+            pending.id == block.id && pending.validator_id == block.validator_id && pending.hash != block.hash
+        });
+        if equivocates {
+            let (validator_id, height) = (block.validator_id.clone(), block.id);
+            // Blok kedua dimasukkan sementara sebagai bukti untuk report_double_sign
+            self.pending_blocks.insert(block.clone());
+            self.report_double_sign(&validator_id, height);
+            self.pending_blocks.remove(&block);
+            return false;
+        }
+        self.pending_blocks.insert(block)
+    }
+
+    // Fungsi untuk mengecek apakah blok masih menunggu validasi
+    pub fn is_pending(&self, block: &Block) -> bool {
+        self.pending_blocks.contains(block)
+    }
+
+    // Fungsi untuk mengambil blok pending dengan id terendah (lalu timestamp terlama) sebagai urutan validasi
+    pub fn next_pending_block(&self) -> Option<&Block> {
+        self.pending_blocks.iter().min_by(|a, b| {
+            (a.id, a.timestamp, &a.hash).cmp(&(b.id, b.timestamp, &b.hash))
+        })
+    }
+
+    // Fungsi untuk mengatur lama masa pemberitahuan perubahan komisi dalam periode
+    pub fn set_commission_notice_periods(&mut self, periods: u64) {
+        self.params.commission_notice_periods = periods;
+    }
+
+    // Fungsi untuk menjadwalkan perubahan komisi validator yang baru berlaku setelah commission_notice_periods
+    // berlalu, sehingga delegator sempat bereaksi; jadwal baru menggantikan jadwal sebelumnya
+    pub fn queue_commission_change(&mut self, validator_id: &str, new_rate: f64) -> Result<(), HybridStakeError> {
+        let effective_period = self.current_period + self.params.commission_notice_periods;
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        validator.pending_commission = Some((new_rate.clamp(0.0, 1.0), effective_period));
+        Ok(())
+    }
+
+    // Fungsi untuk mengatur lama masa pemanasan validator baru dalam periode; hanya berlaku untuk validator
+    // yang ditambahkan setelahnya
+    pub fn set_warmup_periods(&mut self, periods: u64) {
+        self.params.warmup_periods = periods;
+    }
+
+    // Fungsi untuk mengatur panjang epoch dalam periode; perubahan set validator (aktivasi, penjara, komisi)
+    // hanya berlaku di batas epoch. Nilai 0 dianggap 1
+    pub fn set_epoch_length(&mut self, periods: u64) {
+        self.params.epoch_length = periods.max(1);
+    }
+
+    pub fn epoch_length(&self) -> u64 {
+        self.params.epoch_length.max(1)
+    }
+
+    // Fungsi untuk mendapatkan nomor epoch yang sedang berjalan
+    pub fn current_epoch(&self) -> u64 {
+        self.current_period / self.epoch_length()
+    }
+
+    // Fungsi untuk membulatkan periode ke atas ke batas epoch berikutnya (atau periode itu sendiri bila sudah di batas)
+    fn next_epoch_boundary(&self, period: u64) -> u64 {
+        period.div_ceil(self.epoch_length()).saturating_mul(self.epoch_length())
+    }
+
+    // Fungsi untuk mengatur lama masa unbonding dalam periode
+    pub fn set_unbonding_periods(&mut self, periods: u64) {
+        self.params.unbonding_periods = periods;
+    }
+
+    // Fungsi untuk menarik stake validator ke antrean unbonding; stake baru dapat digunakan
+    // setelah unbonding_periods berlalu
+    pub fn request_unbond(&mut self, validator_id: &str, amount: u64) -> Result<(), HybridStakeError> {
+        let unlock_period = self.current_period + self.params.unbonding_periods;
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| StakeError::UnknownValidator(validator_id.to_string()))?;
+        if amount > validator.stake {
+            return Err(StakeError::InsufficientStake { available: validator.stake, requested: amount }.into());
+        }
+        validator.stake -= amount;
+        validator.pending_unbond.push((amount, unlock_period));
+        Ok(())
+    }
+
+    // Fungsi untuk menambah stake validator dari luar chain; dicatat sebagai alokasi, bukan inflasi
+    pub fn add_stake(&mut self, validator_id: &str, amount: u64) -> Result<(), HybridStakeError> {
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        validator.stake += amount;
+        self.allocate_supply(amount);
+        Ok(())
+    }
+
+    // Fungsi untuk menarik stake validator secara sukarela; penarikan melewati antrean unbonding
+    pub fn withdraw_stake(&mut self, validator_id: &str, amount: u64) -> Result<(), HybridStakeError> {
+        self.request_unbond(validator_id, amount)
+    }
+
+    // Fungsi untuk mengatur batas periode terlewat sebelum validator dipenjara
+    pub fn set_jail_threshold(&mut self, periods: u64) {
+        self.params.jail_threshold = periods;
+    }
+
+    // Fungsi untuk mengatur faktor peluruhan reputasi per rotasi; 1.0 menonaktifkan peluruhan. Faktor di luar
+    // (0, 1] ditolak dan nilai lama dipertahankan
+    pub fn set_reputation_decay(&mut self, decay_factor: f64) -> Result<(), HybridStakeError> {
+        check_reputation_decay(decay_factor)?;
+        self.params.reputation_decay = decay_factor;
+        Ok(())
+    }
+
+    // Fungsi untuk meminta validator dikeluarkan dari penjara jika reputasinya masih di atas batas minimum;
+    // pembebasannya baru berlaku di batas epoch berikutnya
+    pub fn unjail(&mut self, validator_id: &str) -> Result<(), HybridStakeError> {
+        let validator = self
+            .validators
+            .get_mut(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        if validator.reputation < UNJAIL_MIN_REPUTATION {
+            return Err(ChainError::ReputationTooLow { reputation: validator.reputation, required: UNJAIL_MIN_REPUTATION }.into());
+        }
+        validator.pending_unjail = true;
+        Ok(())
+    }
+
+    // Fungsi untuk merotasi validator berdasarkan periode
+    pub fn rotate_validators(&mut self) {
+        self.current_period += 1;
+        let current_period = self.current_period;
+        // Perubahan set validator (penjara, pembebasan, dan komisi) hanya diterapkan di batas epoch
+        let epoch_boundary = current_period.is_multiple_of(self.epoch_length());
+        let mut penalties = 0;
+        let mut penalized = Vec::new();
+        // Bayarkan unbonding validator yang sudah keluar setelah masa unbonding-nya selesai
+        let accounts = &mut self.accounts;
+        self.pending_exits.retain(|(payout, amount, unlock_period)| {
+            if *unlock_period <= current_period {
+                *accounts.entry(payout.clone()).or_insert(0) += amount;
+                false
+            } else {
+                true
+            }
+        });
+        for validator in self.validators.values_mut() {
+            // Lepaskan stake yang masa unbonding-nya sudah selesai
+            validator.pending_unbond.retain(|&(amount, unlock_period)| {
+                if unlock_period <= current_period {
+                    validator.unbonded_balance += amount;
+                    false
+                } else {
+                    true
+                }
+            });
+            if epoch_boundary && validator.pending_unjail {
+                validator.pending_unjail = false;
+                validator.jailed = false;
+                validator.missed_periods = 0;
+                // Mulai hitungan ketidakaktifan dari periode sekarang agar tidak langsung dipenjara lagi
+                validator.last_block_validated = current_period;
+            }
+            // Terapkan perubahan komisi yang masa pemberitahuannya sudah selesai
+            if let Some((rate, effective_period)) = validator.pending_commission {
+                if epoch_boundary && effective_period <= current_period {
+                    validator.commission_rate = rate;
+                    validator.pending_commission = None;
+                }
+            }
+            // Validator yang sedang dipenjara sudah keluar dari set dan tidak dihukum lagi; validator yang
+            // masih pemanasan belum pernah dapat dipilih sehingga juga tidak dihukum
+            if validator.jailed || validator.activation_period > current_period {
+                continue;
+            }
+            if self.current_period - validator.last_block_validated >= validator.rotation_period {
+                validator.missed_periods += 1;
+                validator.blocks_missed += 1;
+                validator.clean_periods = 0;
+                if epoch_boundary && validator.missed_periods > self.params.jail_threshold {
+                    validator.jailed = true;
+                    warn!("Validator {} jailed after {} missed periods", validator.id, validator.missed_periods);
+                }
+                let had_stake = validator.stake > 0;
+                // Penalti tumbuh sesuai jumlah periode terlewat berturut-turut agar absen kronis dihukum lebih berat
+                let stake_penalty = validator
+                    .stake
+                    .min(self.params.inactivity_stake_penalty.saturating_mul(validator.missed_periods));
+                penalties += stake_penalty;
+                penalized.push(validator.id.clone());
+                validator.stake -= stake_penalty;
+                validator.reputation = (validator.reputation - self.params.reputation_penalty).clamp(0.0, MAX_REPUTATION); // Decrease reputation
+                if had_stake && validator.stake == 0 {
+                    warn!("Validator {} has been fully penalized to zero stake", validator.id);
+                }
+            } else {
+                // Peluruhan menuju nilai netral 1.0, hanya untuk yang tidak terkena penalti agar keduanya tidak
+                // terhitung ganda. Reputasi di bawah 1.0 tidak ikut ditarik naik karena pemulihannya sudah diatur
+                // lewat reputation_recovery di bawah
+                if validator.reputation > 1.0 {
+                    let decayed = 1.0 + (validator.reputation - 1.0) * self.params.reputation_decay;
+                    validator.reputation = decayed.min(MAX_REPUTATION);
+                }
+                validator.clean_periods += 1;
+                // Validator yang pernah di-slash pulih perlahan; makin banyak pelanggaran makin lambat, dan reputasinya
+                // tertahan di 1.0 sampai cukup periode bersih berlalu
+                let offenses = self.security_measures.validator_penalties.get(&validator.id).copied().unwrap_or(0);
+                if offenses > 0 {
+                    let ceiling = if validator.clean_periods >= self.params.recovery_periods { MAX_REPUTATION } else { 1.0 };
+                    if validator.reputation < ceiling {
+                        let recovery = self.params.reputation_recovery / offenses as f64;
+                        validator.reputation = (validator.reputation + recovery).min(ceiling);
+                    }
+                }
+            }
+        }
+        self.collect_penalty(penalties);
+        penalized.sort();
+        debug!("Rotated validators for period {}, penalized: {:?}", current_period, penalized);
+        self.emit(ChainEvent::ValidatorRotated {
+            period: current_period,
+            penalized,
+        });
+        self.periods_since_last_block += 1;
+        // Peringatan hanya dipancarkan sekali, saat batas pertama kali terlewati
+        if self.stall_threshold > 0 && self.periods_since_last_block == self.stall_threshold + 1 {
+            warn!("Chain stalled: no block for {} periods", self.periods_since_last_block);
+            self.emit(ChainEvent::Stalled {
+                periods_since_last_block: self.periods_since_last_block,
+            });
+        }
+        self.record_validator_history();
+    }
+
+    // Fungsi untuk mencatat state semua validator pada tinggi chain saat ini; periode tanpa blok baru
+    // menimpa catatan terakhir untuk tinggi yang sama, dan catatan lebih dari history_window blok di
+    // bawah tinggi final dibuang
+    fn record_validator_history(&mut self) {
+        let Some(height) = self.blocks.last().map(|tip| tip.id) else {
+            return;
+        };
+        let mut snapshots: Vec<ValidatorSnapshot> = self
+            .validators
+            .values()
+            .map(|validator| ValidatorSnapshot {
+                id: validator.id.clone(),
+                period: self.current_period,
+                stake: validator.stake,
+                delegated_stake: validator.delegated_stake,
+                reputation: validator.reputation,
+                jailed: validator.jailed,
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.id.cmp(&b.id));
+        if self.validator_history.last().is_some_and(|(recorded, _)| *recorded == height) {
+            self.validator_history.pop();
+        }
+        self.validator_history.push((height, snapshots));
+        if let Some(finalized_height) = self.finalized_height {
+            let cutoff = finalized_height.saturating_sub(self.history_window);
+            self.validator_history.retain(|(recorded, _)| *recorded >= cutoff);
+        }
+    }
+
+    // Fungsi untuk mendapatkan state validator sebagaimana tercatat pada tinggi blok `height`, yaitu catatan
+    // terakhir pada atau di bawah tinggi tersebut; None bila tinggi itu sudah di luar jendela riwayat
+    pub fn validator_at(&self, id: &str, height: u64) -> Option<ValidatorSnapshot> {
+        let index = self.validator_history.partition_point(|(recorded, _)| *recorded <= height);
+        let (_, snapshots) = self.validator_history.get(index.checked_sub(1)?)?;
+        snapshots
+            .binary_search_by(|snapshot| snapshot.id.as_str().cmp(id))
+            .ok()
+            .map(|position| snapshots[position].clone())
+    }
+
+    // Fungsi untuk mengatur jumlah blok di bawah tinggi final yang riwayat validatornya dipertahankan
+    pub fn set_history_window(&mut self, blocks: u64) {
+        self.history_window = blocks;
+    }
+
+    pub fn periods_since_last_block(&self) -> u64 {
+        self.periods_since_last_block
+    }
+
+    // Fungsi untuk mengecek apakah chain macet, yaitu lebih dari `threshold` rotasi berlalu tanpa blok baru
+    pub fn is_stalled(&self, threshold: u64) -> bool {
+        self.periods_since_last_block > threshold
+    }
+
+    // Fungsi untuk mengatur batas rotasi tanpa blok sebelum ChainEvent::Stalled dipancarkan; 0 menonaktifkan peringatan
+    pub fn set_stall_threshold(&mut self, threshold: u64) {
+        self.stall_threshold = threshold;
+    }
+
+    // Fungsi untuk mendaftarkan listener yang dipanggil setiap kali blockchain memancarkan peristiwa
+    pub fn on_event(&mut self, f: EventListener) {
+        self.listeners.push(f);
+    }
+
+    fn emit(&mut self, event: ChainEvent) {
+        for listener in &mut self.listeners {
+            listener(&event);
+        }
+    }
+
+    // Fungsi untuk membakar stake hasil penalti (mengurangi total supply) atau memasukkannya ke treasury
+    fn collect_penalty(&mut self, amount: u64) {
+        if self.burn_penalties {
+            self.total_supply = self.total_supply.saturating_sub(amount);
+        } else {
+            self.treasury += amount;
+        }
+    }
+
+    // Fungsi untuk memilih apakah penalti dibakar atau disimpan di treasury
+    pub fn set_burn_penalties(&mut self, burn: bool) {
+        self.burn_penalties = burn;
+    }
+
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    pub fn treasury(&self) -> u64 {
+        self.treasury
+    }
+
+    // Fungsi untuk menghitung inflasi relatif terhadap supply awal (alokasi validator dan token holder)
+    pub fn inflation_rate(&self) -> f64 {
+        if self.supply_baseline == 0 {
+            return 0.0;
+        }
+        (self.total_supply as f64 - self.supply_baseline as f64) / self.supply_baseline as f64
+    }
+
+    // Fungsi untuk menghitung total stake validator yang masih aktif (tidak dipenjara atau tercatat berbuat curang)
+    pub fn total_active_stake(&self) -> u64 {
+        self.validators
+            .values()
+            .filter(|validator| !self.security_measures.malicious_activity_log.contains(&validator.id))
+            .filter(|validator| !validator.jailed)
+            .map(|validator| validator.stake + validator.delegated_stake)
+            .sum()
+    }
+
+    // Fungsi untuk menjumlahkan stake validator berbeda yang membangun blok di atas blok pada indeks tertentu,
+    // dalam jendela finality_threshold blok berikutnya
+    pub fn stake_built_on(&self, index: usize) -> u64 {
+        self.supporting_stake(index, self.producers_after(index))
+    }
+
+    // Fungsi untuk menjumlahkan stake validator yang mendukung blok pada indeks tertentu, baik dengan
+    // membangun blok di atasnya maupun dengan atestasi eksplisit
+    pub fn attested_stake(&self, index: usize) -> u64 {
+        let mut supporters = self.producers_after(index);
+        if let Some(attesters) = self.blocks.get(index).and_then(|block| self.attestations.get(&block.hash)) {
+            supporters.extend(attesters);
+        }
+        self.supporting_stake(index, supporters)
+    }
+
+    // Fungsi untuk mendapatkan total stake aktif yang menjadi pembanding dukungan blok pada indeks tertentu, yaitu
+    // total saat blok itu diterapkan; blok yang sudah final memakai total saat ini
+    pub fn finality_total_stake(&self, index: usize) -> u64 {
+        self.basis_at(index).map_or_else(|| self.total_active_stake(), |basis| basis.total)
+    }
+
+    // Fungsi untuk mendapatkan basis stake suara blok pada indeks tertentu, jika blok itu belum final
+    fn basis_at(&self, index: usize) -> Option<&StakeBasis> {
+        self.blocks.get(index).and_then(|block| self.stake_bases.get(&block.id))
+    }
+
+    // Fungsi untuk membekukan stake suara setiap validator dan total stake aktif dari state sekarang
+    fn stake_basis(&self) -> StakeBasis {
+        StakeBasis {
+            stakes: self
+                .validators
+                .keys()
+                .map(|validator_id| (validator_id.clone(), self.voting_stake(validator_id)))
+                .filter(|&(_, stake)| stake > 0)
+                .collect(),
+            total: self.total_active_stake(),
+        }
+    }
+
+    // Fungsi untuk mengumpulkan produsen blok dalam jendela finality_threshold setelah indeks tertentu
+    fn producers_after(&self, index: usize) -> HashSet<&String> {
+        self.blocks
+            .iter()
+            .skip(index + 1)
+            .take(self.finality_threshold as usize)
+            .map(|block| &block.validator_id)
+            .collect()
+    }
+
+    // Fungsi untuk menjumlahkan stake sekumpulan validator yang mendukung blok pada indeks tertentu menurut basis
+    // stake blok itu (stake saat ini untuk blok yang sudah final), tanpa validator yang tercatat berbuat curang
+    fn supporting_stake(&self, index: usize, validator_ids: HashSet<&String>) -> u64 {
+        let basis = self.basis_at(index);
+        validator_ids
+            .into_iter()
+            .map(|validator_id| match basis {
+                Some(basis) => basis.stakes.get(validator_id).copied().unwrap_or(0),
+                None => self.voting_stake(validator_id),
+            })
+            .sum()
+    }
+
+    // Fungsi untuk mendapatkan stake efektif yang dibawa suara validator; 0 untuk validator yang tidak dikenal
+    // atau tercatat berbuat curang
+    fn voting_stake(&self, validator_id: &str) -> u64 {
+        if self.security_measures.malicious_activity_log.contains(validator_id) {
+            return 0;
+        }
+        self.validators
+            .get(validator_id)
+            .map_or(0, |validator| validator.stake + validator.delegated_stake)
+    }
+
+    // Fungsi untuk mencatat atestasi validator atas blok di chain utama, lalu mengecek ulang finalitas
+    pub fn attest(&mut self, validator_id: &str, block_hash: Hash) -> Result<(), HybridStakeError> {
+        let validator = self
+            .validators
+            .get(validator_id)
+            .ok_or_else(|| ChainError::UnknownValidator(validator_id.to_string()))?;
+        if validator.jailed {
+            return Err(ChainError::ValidatorJailed(validator_id.to_string()).into());
+        }
+        if !self.hash_index.contains_key(&block_hash) {
+            return Err(ChainError::UnknownBlock(block_hash).into());
+        }
+        self.attestations
+            .entry(block_hash)
+            .or_default()
+            .insert(validator_id.to_string());
+        let block_id = self.blocks[self.hash_index[&block_hash]].id;
+        if self.cast_vote(block_id, validator_id) {
+            self.advance_finality(vec![block_id]);
+        }
+        Ok(())
+    }
+
+    pub fn justified_height(&self) -> Option<u64> {
+        self.justified_height
+    }
+
+    pub fn finalized_height(&self) -> Option<u64> {
+        self.finalized_height
+    }
+
+    // Fungsi untuk mengecek apakah blok dengan id tertentu sudah final dan aman diandalkan
+    pub fn is_finalized(&self, block_id: u64) -> bool {
+        self.finalized_height.is_some_and(|height| block_id <= height)
+    }
+
+    // Fungsi untuk mengecek apakah dukungan stake merupakan supermayoritas, yaitu lebih dari 2/3 total;
+    // tepat 2/3 belum cukup. Dengan n validator berstake sama, dibutuhkan floor(2n/3) + 1 pendukung
+    fn is_supermajority(supporting_stake: u64, total_stake: u64) -> bool {
+        total_stake > 0 && supporting_stake as u128 * 3 > total_stake as u128 * 2
+    }
+
+    // Fungsi untuk mengecek finalitas blok dalam dua tahap: blok "justified" jika stake yang mendukungnya
+    // merupakan supermayoritas stake aktif, dan final jika blok berikutnya juga justified. Dukungan dihitung dari
+    // produsen berbeda dalam finality_threshold blok penerus ditambah atestasi, sehingga ambang t kurang dari
+    // jumlah pendukung minimum tidak akan pernah memfinalkan blok tanpa atestasi. Biasanya penghitung suara
+    // diperbarui sedikit demi sedikit setiap ada blok atau atestasi baru; fungsi ini menyusun ulang seluruh
+    // penghitung, misalnya setelah finality_threshold diubah. Kedua cara memakai basis stake yang dibekukan saat
+    // setiap blok diterapkan sehingga hasilnya sama. Mengembalikan id blok yang baru final
+    pub fn check_finality(&mut self) -> Vec<u64> {
+        let justified = self.rebuild_vote_tallies();
+        self.advance_finality(justified)
+    }
+
+    // Fungsi untuk menyusun ulang penghitung suara semua blok setelah blok final, mengembalikan id blok yang justified
+    fn rebuild_vote_tallies(&mut self) -> Vec<u64> {
+        self.vote_tallies.clear();
+        let start = self
+            .finalized_height
+            .map_or(0, |height| self.blocks.partition_point(|block| block.id <= height));
+        let mut justified = Vec::new();
+        for index in start..self.blocks.len() {
+            justified.extend(self.record_votes(index));
+        }
+        justified
+    }
+
+    // Fungsi untuk mencatat suara yang dibawa blok pada indeks tertentu: blok itu mendapat penghitung baru berisi
+    // atestasi yang sudah ada, dan produsennya mendukung finality_threshold blok sebelumnya yang belum final.
+    // Biayanya sebanding dengan finality_threshold, bukan dengan panjang bagian chain yang belum final.
+    // Mengembalikan id blok yang karenanya menjadi justified
+    fn record_votes(&mut self, index: usize) -> Vec<u64> {
+        let block = &self.blocks[index];
+        let (block_id, producer) = (block.id, block.validator_id.clone());
+        let attesters: Vec<String> = self.attestations.get(&block.hash).into_iter().flatten().cloned().collect();
+        self.vote_tallies.insert(block_id, VoteTally::default());
+        let mut justified = Vec::new();
+        for attester in &attesters {
+            if self.cast_vote(block_id, attester) {
+                justified.push(block_id);
+            }
+        }
+        for supported_id in block_id.saturating_sub(self.finality_threshold)..block_id {
+            if self.cast_vote(supported_id, &producer) {
+                justified.push(supported_id);
+            }
+        }
+        justified
+    }
+
+    // Fungsi untuk menambahkan suara validator ke penghitung blok yang belum final. Setiap validator dihitung
+    // sekali per blok dengan stake menurut basis stake blok itu, sehingga urutan suara tidak memengaruhi hasil.
+    // Mengembalikan true jika blok baru saja menjadi justified
+    fn cast_vote(&mut self, block_id: u64, voter: &str) -> bool {
+        let Some(basis) = self.stake_bases.get(&block_id) else {
+            return false;
+        };
+        let (stake, total_stake) = (basis.stakes.get(voter).copied().unwrap_or(0), basis.total);
+        let Some(tally) = self.vote_tallies.get_mut(&block_id) else {
+            return false;
+        };
+        if !tally.voters.insert(voter.to_string()) {
+            return false;
+        }
+        tally.stake += stake;
+        if tally.justified || !Blockchain::is_supermajority(tally.stake, total_stake) {
+            return false;
+        }
+        tally.justified = true;
+        true
+    }
+
+    // Fungsi untuk menaikkan justified_height dari blok yang baru justified, lalu memfinalkan blok tertinggi
+    // yang blok berikutnya juga justified. Hanya pasangan yang melibatkan blok baru justified yang perlu diperiksa.
+    // Mengembalikan id blok yang baru final
+    fn advance_finality(&mut self, newly_justified: Vec<u64>) -> Vec<u64> {
+        let Some(highest) = newly_justified.iter().max().copied() else {
+            return Vec::new();
+        };
+        if self.justified_height.is_none_or(|height| highest > height) {
+            self.justified_height = Some(highest);
+        }
+        let is_justified = |block_id: u64| self.vote_tallies.get(&block_id).is_some_and(|tally| tally.justified);
+        let finalized_height = newly_justified
+            .iter()
+            .flat_map(|&block_id| [block_id.checked_sub(1), Some(block_id)])
+            .flatten()
+            .filter(|&block_id| is_justified(block_id) && is_justified(block_id + 1))
+            .max();
+        let finalized_height = match finalized_height {
+            Some(height) => height,
+            None => return Vec::new(),
+        };
+        // Finalitas bersifat monoton: blok di bawah finalized_height tidak dapat dibatalkan
+        if self.finalized_height.is_some_and(|height| height >= finalized_height) {
+            return Vec::new();
+        }
+        // Semua leluhur dari blok final juga final
+        let first_unfinalized = self.finalized_height.map_or(0, |height| height + 1);
+        let newly_finalized: Vec<u64> = self
+            .blocks_in_range(first_unfinalized, finalized_height + 1)
+            .map(|block| block.id)
+            .collect();
+        self.finalized_height = Some(finalized_height);
+        // Blok final tidak akan dinilai lagi sehingga penghitungnya dibuang
+        self.vote_tallies = self.vote_tallies.split_off(&(finalized_height + 1));
+        self.stake_bases = self.stake_bases.split_off(&(finalized_height + 1));
+        info!("Finalized up to block #{} ({} newly finalized)", finalized_height, newly_finalized.len());
+        self.emit(ChainEvent::FinalityReached {
+            finalized_height,
+            block_ids: newly_finalized.clone(),
+        });
+        newly_finalized
+    }
+
+    // Fungsi untuk melaporkan validator yang menandatangani dua blok berbeda pada ketinggian yang sama
+    pub fn report_double_sign(&mut self, validator_id: &str, height: u64) -> bool {
+        let signed_hashes: HashSet<Hash> = self
+            .blocks
+            .iter()
+            .chain(self.side_blocks.values())
+            .chain(self.pending_blocks.iter())
+            .filter(|block| block.id == height && block.validator_id == validator_id)
+            .map(|block| block.hash)
+            .collect();
+        if signed_hashes.len() < 2 {
+            return false;
+        }
+        // Bukti yang sama tidak boleh dihitung sebagai pelanggaran berulang
+        if !self.validators.contains_key(validator_id)
+            || !self.security_measures.reported_double_signs.insert((validator_id.to_string(), height))
+        {
+            return false;
+        }
+        self.penalize(validator_id).is_some()
+    }
+
+    // Fungsi untuk menghukum pelanggaran validator dengan slash yang meningkat sesuai jumlah pelanggaran sebelumnya,
+    // mengembalikan jumlah stake yang dipotong
+    fn penalize(&mut self, validator_id: &str) -> Option<u64> {
+        let validator = self.validators.get_mut(validator_id)?;
+        let offenses = self
+            .security_measures
+            .validator_penalties
+            .entry(validator_id.to_string())
+            .or_insert(0);
+        let slash_percent = ESCALATING_SLASH_PERCENTS.get(*offenses as usize).copied();
+        *offenses += 1;
+        let offense = *offenses;
+        let slash = match slash_percent {
+            Some(percent) => validator.stake * percent / 100,
+            None => {
+                // Pelanggar berulang dilarang permanen
+                self.security_measures.malicious_activity_log.insert(validator_id.to_string());
+                0
+            }
+        };
+        validator.stake = validator.stake.saturating_sub(slash);
+        validator.reputation = 0.0;
+        validator.clean_periods = 0;
+        self.collect_penalty(slash);
+        warn!("Validator {} slashed by {} for offense #{}", validator_id, slash, offense);
+        self.emit(ChainEvent::Slashed {
+            validator_id: validator_id.to_string(),
+            amount: slash,
+        });
+        Some(slash)
+    }
+
+    // Fungsi untuk mendapatkan jumlah pelanggaran yang tercatat untuk validator
+    pub fn penalty_count(&self, validator_id: &str) -> u64 {
+        self.security_measures.validator_penalties.get(validator_id).copied().unwrap_or(0)
+    }
+
+    // Fungsi untuk menghitung ulang delegated stake seluruh validator dari delegasi token holder, dimulai dari nol
+    // agar tidak terakumulasi tiap periode; dipakai untuk memperbaiki selisih setelah perubahan struktur chain
+    pub fn recompute_delegated_stake(&mut self) {
+        for validator in self.validators.values_mut() {
+            validator.delegated_stake = 0;
+        }
+        for holder in self.token_holders.values() {
+            for (validator_id, amount) in &holder.delegated_to {
+                if let Some(validator) = self.validators.get_mut(validator_id) {
+                    validator.delegated_stake += amount;
+                }
+            }
+        }
+    }
+
+    // Fungsi utama untuk menjalankan algoritma HybridStake
+    pub fn run_hybrid_stake(&mut self) -> Result<Option<u64>, HybridStakeError> {
+        self.recompute_delegated_stake();
+        let produced = self.produce_block();
+
+        // Simulasikan periode rotasi validator, tetap berjalan walaupun tidak ada blok yang dihasilkan
+        self.rotate_validators();
+        Ok(produced.map(Some)?)
+    }
+
+    // Fungsi untuk menjalankan run_hybrid_stake sebanyak `periods` periode dan merangkum hasilnya
+    pub fn simulate(&mut self, periods: u64) -> SimulationReport {
+        let slashes = self.start_slash_recorder();
+        let start = self.blocks.len();
+        let mut failed_periods = 0;
+        for _ in 0..periods {
+            if self.run_hybrid_stake().is_err() {
+                failed_periods += 1;
+            }
+        }
+        let slashes = self.stop_slash_recorder(&slashes);
+
+        let mut blocks_by_validator = HashMap::new();
+        for block in self.blocks.iter().skip(start) {
+            *blocks_by_validator.entry(block.validator_id.clone()).or_insert(0) += 1;
+        }
+        SimulationReport {
+            periods,
+            blocks_produced: self.blocks.len().saturating_sub(start) as u64,
+            failed_periods,
+            blocks_by_validator,
+            slashes,
+            finalized_height: self.finalized_height,
+        }
+    }
+
+    // Fungsi untuk mensimulasikan serangan sekelompok validator yang berkolusi: setiap kali penyerang
+    // menghasilkan blok, ia juga menerbitkan blok tandingan pada ketinggian yang sama (double sign) yang
+    // kemudian dilaporkan, sehingga laporan menunjukkan seberapa cepat mekanisme keamanan menindaknya.
+    // Simulasi dijalankan pada salinan chain sehingga chain ini tidak berubah
+    pub fn simulate_attack(&self, attacker_ids: &[String], periods: u64) -> AttackReport {
+        let mut sandbox = Blockchain::from_snapshot(self.to_snapshot());
+        sandbox.side_blocks = self.side_blocks.clone();
+        sandbox.pending_blocks = self.pending_blocks.clone();
+        sandbox.mempool = self.mempool.clone();
+        sandbox.signing_keys = self.signing_keys.clone();
+        sandbox.vrf_keys = self.vrf_keys.clone();
+        // Jam dan pemilih produsen tidak dapat disalin, jadi dibagi dengan salinan selama simulasi
+        sandbox.clock = Arc::clone(&self.clock);
+        sandbox.proposer_selector = Arc::clone(&self.proposer_selector);
+        sandbox.run_attack(attacker_ids, periods)
+    }
+
+    // Fungsi untuk menjalankan simulasi serangan simulate_attack langsung pada chain ini
+    fn run_attack(&mut self, attacker_ids: &[String], periods: u64) -> AttackReport {
+        let slashes = self.start_slash_recorder();
+        let start = self.blocks.len();
+        let mut attacker_blocks = 0;
+        let mut double_signs_detected = 0;
+        for _ in 0..periods {
+            if !matches!(self.run_hybrid_stake(), Ok(Some(_))) {
+                continue;
+            }
+            let block = self.blocks.last().expect("a block was just produced");
+            if !attacker_ids.contains(&block.validator_id) {
+                continue;
+            }
+            attacker_blocks += 1;
+            let mut conflicting = Block::with_timestamp(
+                block.id,
+                vec![Transaction::memo(format!("conflicting block by {}", block.validator_id))],
+                block.validator_id.clone(),
+                block.previous_hash,
+                block.timestamp,
+                self.hash_algorithm,
+            );
+            self.sign_block(&mut conflicting);
+            let (validator_id, height) = (conflicting.validator_id.clone(), conflicting.id);
+            self.side_blocks.insert(conflicting.hash, conflicting);
+            if self.report_double_sign(&validator_id, height) {
+                double_signs_detected += 1;
+            }
+        }
+        let slashes = self.stop_slash_recorder(&slashes);
+
+        let mut banned: Vec<String> = attacker_ids
+            .iter()
+            .filter(|id| self.security_measures.malicious_activity_log.contains(*id))
+            .cloned()
+            .collect();
+        banned.sort();
+        AttackReport {
+            periods,
+            blocks_produced: self.blocks.len().saturating_sub(start) as u64,
+            attacker_blocks,
+            double_signs_detected,
+            slashes,
+            banned,
+        }
+    }
+
+    // Fungsi untuk memasang listener sementara yang mencatat setiap slash
+    fn start_slash_recorder(&mut self) -> Arc<Mutex<Vec<(String, u64)>>> {
+        let slashes = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&slashes);
+        self.on_event(Box::new(move |event| {
+            if let ChainEvent::Slashed { validator_id, amount } = event {
+                recorder.lock().expect("slash recorder is not poisoned").push((validator_id.clone(), *amount));
+            }
+        }));
+        slashes
+    }
+
+    // Fungsi untuk melepas listener dari start_slash_recorder dan mengambil slash yang tercatat
+    fn stop_slash_recorder(&mut self, slashes: &Mutex<Vec<(String, u64)>>) -> Vec<(String, u64)> {
+        // Listener perekam hanya berlaku selama simulasi
+        self.listeners.pop();
+        std::mem::take(&mut *slashes.lock().expect("slash recorder is not poisoned"))
+    }
+
+    // Fungsi untuk memilih validator lalu membangun, menandatangani, dan memvalidasi blok berikutnya,
+    // mengembalikan id blok yang dihasilkan
+    fn produce_block(&mut self) -> Result<u64, ChainError> {
+        // Pilih validator berdasarkan stake dan reputasi
+        let (validator_id, vrf_proof) = if self.selection_strategy == SelectionStrategy::Vrf {
+            let (slot, validator_id, output, proof) = self.vrf_lottery().ok_or(ChainError::NoEligibleValidator)?;
+            (validator_id, Some((slot, output, proof)))
+        } else {
+            (self.select_validator().ok_or(ChainError::NoEligibleValidator)?.id.clone(), None)
+        };
+        let previous_hash = self.blocks.last().map(|tip| tip.hash).unwrap_or_default();
+        // Ambil transaksi ber-fee tertinggi yang dapat diterapkan lebih dulu (urutan masuk dipertahankan
+        // untuk fee yang sama) selama masih muat dalam batas ukuran blok; transaksi yang nonce-nya belum
+        // gilirannya dicoba lagi setelah pendahulunya masuk, sedangkan yang tidak valid dilewati
+        let mut candidates: Vec<&Transaction> = self.mempool.iter().collect();
+        candidates.sort_by_key(|tx| std::cmp::Reverse(tx.fee));
+        let mut changes = AccountChanges::default();
+        let mut payload_size = 0;
+        let mut transactions = Vec::new();
+        while transactions.len() < self.max_txs_per_block {
+            let next = match candidates.iter().position(|tx| self.apply_transaction(&mut changes, tx).is_ok()) {
+                Some(index) => candidates.remove(index),
+                None => break,
+            };
+            payload_size += next.canonical_bytes().len();
+            if payload_size > self.max_block_size {
+                break;
+            }
+            transactions.push(next.clone());
+        }
+        let mut block = Block::with_timestamp(
+            self.height(),
+            transactions,
+            validator_id,
+            previous_hash,
+            self.clock.now_millis(),
+            self.hash_algorithm,
+        );
+        if let Some((slot, output, proof)) = vrf_proof {
+            block.attach_vrf_proof(slot, output, proof);
+        }
+        // Proof-of-work dilakukan sebelum tanda tangan karena tanda tangan mencakup hash akhir
+        block.mine(self.difficulty);
+        self.sign_block(&mut block);
+        let block_id = block.id;
+        self.accept_block(block).map_err(ChainError::InvalidBlock)?;
+        Ok(block_id)
+    }
+
+    // Fungsi untuk mencatat alokasi awal stake; alokasi ikut menaikkan baseline sehingga tidak dihitung sebagai inflasi
+    fn allocate_supply(&mut self, amount: u64) {
+        self.total_supply += amount;
+        self.supply_baseline += amount;
+    }
+
+    // Fungsi untuk menambah validator; id yang sudah terdaftar ditolak agar state-nya tidak tertimpa
+    pub fn add_validator(&mut self, id: String, stake: u64, rotation_period: u64) -> Result<(), HybridStakeError> {
+        if self.validators.contains_key(&id) {
+            return Err(ChainError::ValidatorExists(id).into());
+        }
+        self.allocate_supply(stake);
+        // Validator baru masuk set pada batas epoch pertama setelah masa pemanasan selesai; jika active set epoch
+        // berjalan sudah dibekukan, batas berikutnya yang dipakai
+        let mut activation_period = self.next_epoch_boundary(self.current_period + self.params.warmup_periods);
+        if activation_period == self.current_period && self.frozen_active_set().is_some() {
+            activation_period += self.epoch_length();
+        }
+        self.validators.insert(
+            id.clone(),
+            Validator {
+                id,
+                stake,
+                delegated_stake: 0,
+                rotation_period,
+                // Hitungan ketidakaktifan dimulai sejak validator boleh dipilih
+                last_block_validated: activation_period,
+                reputation: 1.0,
+                commission_rate: self.params.default_commission_rate,
+                pending_commission: None,
+                pending_unbond: Vec::new(),
+                unbonded_balance: 0,
+                jailed: false,
+                pending_unjail: false,
+                missed_periods: 0,
+                activation_period,
+                clean_periods: 0,
+                blocks_produced: 0,
+                blocks_missed: 0,
+                // Blok validator ditolak sampai kuncinya didaftarkan lewat register_signing_key, atau set_public_key
+                // untuk validator yang kunci privatnya dipegang di luar chain
+                public_key: None,
+                vrf_public_key: None,
+            },
+        );
+        Ok(())
+    }
+
+    // Fungsi untuk menghapus validator; delegasi yang mengarah kepadanya dicabut sehingga stake token holder
+    // tidak lagi dihitung untuk validator yang tidak ada. Stake validator (termasuk yang masih atau sudah selesai
+    // unbonding) ikut keluar dari peredaran sehingga dikurangkan dari total supply
+    pub fn remove_validator(&mut self, id: &str) -> Result<(), HybridStakeError> {
+        let validator = self.detach_validator(id)?;
+        let pending: u64 = validator.pending_unbond.iter().map(|(amount, _)| amount).sum();
+        let removed = validator.stake + validator.unbonded_balance + pending;
+        self.total_supply = self.total_supply.saturating_sub(removed);
+        Ok(())
+    }
+
+    // Fungsi untuk melepas validator dari chain beserta kuncinya dan delegasi yang mengarah kepadanya, tanpa
+    // mengubah supply; mengembalikan state validator yang dilepas
+    fn detach_validator(&mut self, id: &str) -> Result<Validator, ChainError> {
+        let validator = self
+            .validators
+            .remove(id)
+            .ok_or_else(|| ChainError::UnknownValidator(id.to_string()))?;
+        self.signing_keys.remove(id);
+        self.vrf_keys.remove(id);
+        for holder in self.token_holders.values_mut() {
+            holder.delegated_to.retain(|(validator_id, _)| validator_id != id);
+        }
+        self.recompute_delegated_stake();
+        Ok(validator)
+    }
+
+    // Fungsi untuk mengeluarkan validator secara sukarela: stake dan saldo yang sudah selesai unbonding dikirim
+    // ke akun payout, delegasinya dicabut, dan jumlah yang dibayarkan sekarang dikembalikan. Stake yang masih dalam
+    // antrean unbonding tetap dibayarkan ke akun yang sama ketika masa unbonding-nya selesai
+    pub fn exit_validator(&mut self, validator_id: &str, payout: &str) -> Result<u64, HybridStakeError> {
+        let validator = self.detach_validator(validator_id)?;
+        let withdrawable = validator.stake + validator.unbonded_balance;
+        *self.accounts.entry(payout.to_string()).or_insert(0) += withdrawable;
+        self.pending_exits.extend(
+            validator
+                .pending_unbond
+                .into_iter()
+                .map(|(amount, unlock_period)| (payout.to_string(), amount, unlock_period)),
+        );
+        Ok(withdrawable)
+    }
+
+    // Fungsi untuk menambah token holder; id yang sudah terdaftar dan delegasi ke validator yang tidak dikenal ditolak
+    pub fn add_token_holder(&mut self, id: String, stake: u64, delegated_to: Option<String>) -> Result<(), HybridStakeError> {
+        let delegated_to = delegated_to.map(|validator_id| vec![(validator_id, stake)]).unwrap_or_default();
+        self.add_token_holder_multi(id, stake, delegated_to)
+    }
+
+    // Fungsi untuk memindahkan seluruh delegasi token holder ke validator lain, atau mencabutnya dengan None
+    pub fn redelegate(&mut self, holder_id: &str, new_validator: Option<String>) -> Result<(), HybridStakeError> {
+        if let Some(validator_id) = &new_validator {
+            if !self.validators.contains_key(validator_id) {
+                return Err(DelegationError::UnknownValidator(validator_id.clone()).into());
+            }
+        }
+        let holder = self
+            .token_holders
+            .get_mut(holder_id)
+            .ok_or_else(|| DelegationError::UnknownHolder(holder_id.to_string()))?;
+        for (validator_id, amount) in holder.delegated_to.drain(..) {
+            if let Some(validator) = self.validators.get_mut(&validator_id) {
+                validator.delegated_stake = validator.delegated_stake.saturating_sub(amount);
+            }
+        }
+        if let Some(validator_id) = new_validator {
+            if let Some(validator) = self.validators.get_mut(&validator_id) {
+                validator.delegated_stake += holder.stake;
+            }
+            holder.delegated_to.push((validator_id, holder.stake));
+        }
+        Ok(())
+    }
+
+    // Fungsi untuk menambah token holder yang mendelegasikan stake ke beberapa validator; delegated_stake setiap
+    // validator tujuan langsung bertambah sehingga tidak perlu menunggu recompute_delegated_stake
+    pub fn add_token_holder_multi(&mut self, id: String, stake: u64, delegated_to: Vec<(String, u64)>) -> Result<(), HybridStakeError> {
+        let delegated: u64 = delegated_to.iter().map(|(_, amount)| amount).sum();
+        if delegated > stake {
+            return Err(DelegationError::ExceedsStake { stake, delegated }.into());
+        }
+        if self.token_holders.contains_key(&id) {
+            return Err(DelegationError::HolderExists(id).into());
+        }
+        if let Some((validator_id, _)) = delegated_to.iter().find(|(validator_id, _)| !self.validators.contains_key(validator_id)) {
+            return Err(DelegationError::UnknownValidator(validator_id.clone()).into());
+        }
+        for (validator_id, amount) in &delegated_to {
+            if let Some(validator) = self.validators.get_mut(validator_id) {
+                validator.delegated_stake += amount;
+            }
+        }
+        self.allocate_supply(stake);
+        self.token_holders.insert(
+            id.clone(),
+            TokenHolder {
+                id,
+                stake,
+                delegated_to,
+            },
+        );
+        Ok(())
+    }
+}
+
+// Builder untuk menyusun Blockchain dengan banyak opsi tanpa daftar argumen yang terus bertambah
+pub struct BlockchainBuilder {
+    finality_threshold: u64,
+    hash_algorithm: HashKind,
+    params: ChainParams,
+    min_stake: u64,
+    difficulty: usize,
+    strategy: SelectionStrategy,
+    seed: Option<u64>,
+}
+
+impl Default for BlockchainBuilder {
+    fn default() -> Self {
+        BlockchainBuilder {
+            finality_threshold: 1,
+            hash_algorithm: HashKind::Sha256,
+            params: ChainParams::default(),
+            min_stake: 0,
+            difficulty: 0,
+            strategy: SelectionStrategy::default(),
+            seed: None,
+        }
+    }
+}
+
+impl BlockchainBuilder {
+    pub fn new() -> Self {
+        BlockchainBuilder::default()
+    }
+
+    pub fn finality_threshold(mut self, finality_threshold: u64) -> Self {
+        self.finality_threshold = finality_threshold;
+        self
+    }
+
+    pub fn hash_algorithm(mut self, hash_algorithm: HashKind) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    pub fn params(mut self, params: ChainParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn block_reward(mut self, block_reward: u64) -> Self {
+        self.params.block_reward = block_reward;
+        self
+    }
+
+    pub fn min_stake(mut self, min_stake: u64) -> Self {
+        self.min_stake = min_stake;
+        self
+    }
+
+    pub fn delegated_stake_multiplier(mut self, multiplier: f64) -> Self {
+        self.params.delegated_stake_multiplier = multiplier;
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: usize) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    pub fn strategy(mut self, strategy: SelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // Fungsi untuk membuat blockchain dari opsi yang sudah diatur; konfigurasinya diperiksa seperti try_new
+    pub fn build(self) -> Result<Blockchain, HybridStakeError> {
+        let mut blockchain = Blockchain::try_new(ChainConfig {
+            finality_threshold: self.finality_threshold,
+            hash_algorithm: self.hash_algorithm,
+            params: self.params,
+            min_stake: self.min_stake,
+        })?;
+        blockchain.difficulty = self.difficulty;
+        blockchain.selection_strategy = self.strategy;
+        blockchain.seed = self.seed;
+        Ok(blockchain)
+    }
+}
+
+// Pembungkus blockchain yang aman dipakai bersama antar thread; setiap metode mengunci chain secara internal
+// dan clone() berbagi chain yang sama
+#[derive(Clone)]
+pub struct SharedChain {
+    inner: Arc<Mutex<Blockchain>>,
+}
+
+impl SharedChain {
+    pub fn new(blockchain: Blockchain) -> Self {
+        SharedChain {
+            inner: Arc::new(Mutex::new(blockchain)),
+        }
+    }
+
+    // Fungsi untuk mengunci chain secara langsung bagi operasi yang tidak memiliki metode pembungkus
+    pub fn lock(&self) -> MutexGuard<'_, Blockchain> {
+        self.inner.lock().expect("blockchain lock is not poisoned")
+    }
+
+    pub fn submit_transaction(&self, tx: Transaction) -> bool {
+        self.lock().submit_transaction(tx)
+    }
+
+    pub fn latest_block(&self) -> Option<Block> {
+        self.lock().latest_block().cloned()
+    }
+
+    pub fn stats(&self) -> ChainStats {
+        self.lock().stats()
+    }
+
+    pub fn head_info(&self) -> HeadInfo {
+        self.lock().head_info()
+    }
+
+    pub fn handle_query(&self, query: Query) -> QueryResponse {
+        self.lock().handle_query(query)
+    }
+
+    pub fn run_hybrid_stake(&self) -> Result<Option<u64>, HybridStakeError> {
+        self.lock().run_hybrid_stake()
+    }
+}
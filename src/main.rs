@@ -0,0 +1,62 @@
+use clap::Parser;
+use ed25519_dalek::SigningKey;
+use hybrid_stake::Blockchain;
+use rand::{thread_rng, Rng};
+
+// Argumen baris perintah untuk menjalankan simulasi HybridStake tanpa kompilasi ulang
+#[derive(Debug, Parser)]
+#[command(about = "Run a HybridStake consensus simulation")]
+struct Args {
+    #[arg(long, default_value_t = 3, help = "Number of validators")]
+    validators: usize,
+    #[arg(long, default_value_t = 3, help = "Number of token holders, delegating to validators in turn")]
+    holders: usize,
+    #[arg(long, default_value_t = 20, help = "Number of periods to simulate")]
+    periods: u64,
+    #[arg(long, help = "Simulation seed for reproducible proposer selection")]
+    seed: Option<u64>,
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..), help = "Number of successor blocks counted as finality support")]
+    finality: u64,
+}
+
+fn main() {
+    env_logger::init();
+    let args = Args::parse();
+    let mut blockchain = Blockchain::new(args.finality);
+    blockchain.set_seed(args.seed);
+
+    // Inisialisasi validator dan token holder
+    for v in 1..=args.validators {
+        let stake = 100 + 50 * (v as u64 % 3);
+        let validator_id = format!("Validator{}", v);
+        blockchain.add_validator(validator_id.clone(), stake, 10).expect("validator ids are unique");
+        // Kunci tanda tangan validator simulasi dibuat di sini, bukan oleh library
+        blockchain
+            .register_signing_key(&validator_id, SigningKey::from_bytes(&thread_rng().gen()))
+            .expect("validator was just added");
+    }
+    for h in 1..=args.holders {
+        let delegated_to = (args.validators > 0).then(|| format!("Validator{}", (h - 1) % args.validators + 1));
+        blockchain.add_token_holder(format!("Holder{}", h), 50, delegated_to).expect("token holder ids are unique");
+    }
+
+    // Jalankan algoritma HybridStake
+    let report = blockchain.simulate(args.periods);
+
+    // Tampilkan blok yang sudah divalidasi
+    for block in blockchain.blocks() {
+        println!("{}", block);
+    }
+    println!(
+        "Produced {} blocks in {} periods ({} failed), finalized height: {:?}",
+        report.blocks_produced, report.periods, report.failed_periods, report.finalized_height
+    );
+    let mut producers: Vec<_> = report.blocks_by_validator.iter().collect();
+    producers.sort();
+    for (validator_id, blocks) in producers {
+        println!("  {}: {} blocks", validator_id, blocks);
+    }
+    for (validator_id, amount) in &report.slashes {
+        println!("  slashed {}: {}", validator_id, amount);
+    }
+}
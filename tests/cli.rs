@@ -0,0 +1,20 @@
+#[test]
+fn cli_runs_simulation() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hybrid_stake"))
+        .args(["--validators", "4", "--holders", "6", "--periods", "12", "--seed", "9", "--finality", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Produced 12 blocks in 12 periods (0 failed)"), "{}", stdout);
+    assert_eq!(stdout.lines().filter(|line| line.starts_with("Block #")).count(), 12);
+}
+
+#[test]
+fn cli_rejects_zero_finality() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hybrid_stake"))
+        .args(["--finality", "0"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}